@@ -1,37 +1,74 @@
 use super::*;
 use entropy::ValueIter;
 use entropy_field::*;
+use size::*;
 
-/// あるEntropyFieldが与えられた時、一番前方にある収束していないエントロピーの
-/// 位置とエントロピーの値を返します。
-fn first_entropy(field: &EntropyField) -> (Place, Entropy) {
-    for i in 0..CELLS_COUNT {
-        let place = unsafe { Place::new_from_raw_unchecked(i) };
-        if field.entropy_at(&place).len() > 1 {
-            let entropy = field.entropy_at(&place).to_owned();
-            return (place, entropy);
+/// Attackerで探索できる盤面を表すトレイトです。
+#[allow(clippy::len_without_is_empty)]
+pub trait Searchable: Clone {
+    /// 盤面の大きさ
+    type Size: Size;
+    /// 盤面上の位置
+    type Place: Clone + std::fmt::Display;
+    /// ルール違反が検出された際のエラー
+    type Error: std::fmt::Debug + std::fmt::Display;
+    /// 現在確認できたエントロピーの総量を返します。
+    fn len(&self) -> f64;
+    /// 一番前方にある収束していないエントロピーの位置とエントロピーの値を返します。
+    /// 全てのセルが収束している場合はNoneを返します。
+    fn first_entropy(&self) -> Option<(Self::Place, Entropy<Self::Size>)>;
+    /// 指定された位置のセルに値を適用します。
+    fn insert(&mut self, place: Self::Place, value: Value<Self::Size>) -> Result<(), Self::Error>;
+}
+
+impl<S: Size> Searchable for EntropyField<S> {
+    type Size = S;
+    type Place = Place<S>;
+    type Error = RuleViolationError<S>;
+    fn len(&self) -> f64 {
+        EntropyField::len(self)
+    }
+    fn first_entropy(&self) -> Option<(Place<S>, Entropy<S>)> {
+        for i in 0..S::CELLS {
+            let place = unsafe { Place::new_from_raw_unchecked(i) };
+            if self.entropy_at(&place).len() > 1 {
+                let entropy = self.entropy_at(&place).to_owned();
+                return Some((place, entropy));
+            }
         }
+        None
+    }
+    fn insert(&mut self, place: Place<S>, value: Value<S>) -> Result<(), RuleViolationError<S>> {
+        EntropyField::insert(self, place, value)
     }
-    unreachable!();
 }
 
+/// 探索の途中の盤面と、仮定している位置と、まだ仮定していない値の組です。
+type Branch<F> = (
+    F,
+    <F as Searchable>::Place,
+    ValueIter<<F as Searchable>::Size>,
+);
+
 /// 総当たりで探索を行う構造体です。
 /// 1つの解答が見つかった場合も複数解答の可能性を考慮し終了はしません。
-pub struct Attacker(Vec<(EntropyField, Place, ValueIter)>);
-impl Attacker {
-    pub fn new(field: EntropyField) -> Self {
-        let (place, entropy) = first_entropy(&field);
+/// 仮定の間の伝播は盤面に追加されたルールのみで行われるため、
+/// 探索を絞り込むには`LockedCandidates`などを盤面に追加しておきます。
+pub struct Attacker<F: Searchable = EntropyField>(Vec<Branch<F>>);
+impl<F: Searchable> Attacker<F> {
+    pub fn new(field: F) -> Self {
+        let (place, entropy) = field.first_entropy().unwrap();
         Attacker(vec![(field, place, entropy.into_iter())])
     }
 }
-impl From<EntropyField> for Attacker {
-    fn from(field: EntropyField) -> Self {
+impl<F: Searchable> From<F> for Attacker<F> {
+    fn from(field: F) -> Self {
         Attacker::new(field)
     }
 }
 
-impl Iterator for Attacker {
-    type Item = Report;
+impl<F: Searchable> Iterator for Attacker<F> {
+    type Item = Report<F>;
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((field, place, mut iter)) = self.0.pop() {
             if let Some(value) = iter.next() {
@@ -47,7 +84,7 @@ impl Iterator for Attacker {
                                 place,
                                 result: Ok(next_field.clone()),
                             });
-                            let (place, entropy) = first_entropy(&next_field);
+                            let (place, entropy) = next_field.first_entropy().unwrap();
                             self.0.push((next_field, place, entropy.into_iter()));
                             res
                         }
@@ -68,19 +105,19 @@ impl Iterator for Attacker {
 }
 
 /// Attackerによるステップのレポートを返します。
-pub enum Report {
+pub enum Report<F: Searchable = EntropyField> {
     /// 一つの解答が見つかったことを示します。
-    Found(EntropyField),
+    Found(F),
     /// 1つの試行の結果、解答は導けず、
     /// その試行の結果を示します。
     Try {
         /// 仮定された値
-        value: Value,
+        value: Value<F::Size>,
         /// 仮定された場所
-        place: Place,
-        /// Ok(_)の場合は仮定した結果のEntropyFieldが返されます。
+        place: F::Place,
+        /// Ok(_)の場合は仮定した結果の盤面が返されます。
         /// Err(_)の場合は仮定した結果エントロピーの競合が発生したため
         /// その競合のエラーが返されます。
-        result: Result<EntropyField, RuleViolationError>,
+        result: Result<F, F::Error>,
     },
 }
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::places;
+    fn arrow(circle: (usize, usize), cells: &[(usize, usize)]) -> Arrow {
+        Arrow::new(Place::new(circle.0, circle.1).unwrap(), places(cells)).unwrap()
+    }
+    #[test]
+    fn bounds() {
+        let mut field = EntropyField::new();
+        field
+            .add_constraint(arrow((0, 0), &[(1, 1), (2, 2)]))
+            .unwrap();
+        let circle = Place::new(0, 0).unwrap();
+        assert!(!field.entropy_at(&circle).is_possible(&Value::ONE));
+        assert_eq!(
+            field.entropy_at(&Place::new(1, 1).unwrap()),
+            &Entropy::try_from(0b111111110).unwrap()
+        );
+        field.insert(circle.clone(), Value::THREE).unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(2, 2).unwrap()),
+            &Entropy::try_from(0b110).unwrap()
+        );
+        field.insert(Place::new(1, 1).unwrap(), Value::ONE).unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(2, 2).unwrap()),
+            &Value::TWO.into()
+        );
+
+        let mut field = EntropyField::new();
+        field.insert(Place::new(5, 5).unwrap(), Value::TWO).unwrap();
+        // 矢印の合計は3以上となるので、(5, 5)の2とは矛盾する。
+        let error = field
+            .add_constraint(arrow((5, 5), &[(6, 6), (7, 7), (8, 8)]))
+            .unwrap_err();
+        assert_eq!(
+            error.rule().unwrap().to_string(),
+            "矢印: (5, 5) <- (6, 6) <- (7, 7) <- (8, 8)"
+        );
+    }
+}
+
+use super::*;
+
+/// 矢印数独の矢印です。
+/// 丸のセルの値が、矢印上のセルの値の合計と等しくなります。
+/// 矢印上のセルは、同じ行や列、ボックスに含まれない限り同じ値となってもかまいません。
+/// 矢印上のセルの最小値と最大値の合計から丸のセルの値の範囲を絞り込み、
+/// 丸のセルの範囲から矢印上の各セルの値の範囲を絞り込みます。
+#[derive(Debug, Clone)]
+pub struct Arrow<S: Size = Standard> {
+    /// 丸のセル
+    circle: Place<S>,
+    /// 丸に近い方から順に並べた矢印上のセル
+    cells: Vec<Place<S>>,
+}
+
+impl<S: Size> Arrow<S> {
+    /// 丸のセルと、丸に近い方から順に並べた矢印上のセルから新しいArrowを返します。
+    /// 矢印上のセルが空である、重複している、または丸のセルを含む場合はNoneが返ります。
+    pub fn new(circle: Place<S>, cells: Vec<Place<S>>) -> Option<Self> {
+        (is_path(&cells) && !cells.contains(&circle)).then_some(Arrow { circle, cells })
+    }
+    /// 丸のセルを返します。
+    pub fn circle(&self) -> &Place<S> {
+        &self.circle
+    }
+    /// 丸に近い方から順に並べた矢印上のセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+}
+
+impl<S: Size> std::fmt::Display for Arrow<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "矢印: {}", self.circle)?;
+        for place in &self.cells {
+            write!(f, " <- {place}")?;
+        }
+        Ok(())
+    }
+}
+
+/// minからmaxまでの値を表すビット列を返します。
+pub(super) fn range(min: BITS, max: BITS) -> BITS {
+    let max = max.min(BITS::BITS - 1);
+    if min > max {
+        return 0;
+    }
+    (BITS::MAX >> (BITS::BITS - 1 - max)) & (BITS::MAX << min)
+}
+
+impl<S: Size> Constraint<S> for Arrow<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.circle, Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if &self.circle != place && !self.cells.contains(place) {
+            return Ok(());
+        }
+        let bounds: Vec<(BITS, BITS)> = self
+            .cells
+            .iter()
+            .map(|place| {
+                let bits: BITS = cx.entropy_at(place).to_owned().into();
+                (bits.trailing_zeros(), BITS::BITS - 1 - bits.leading_zeros())
+            })
+            .collect();
+        let min_sum: BITS = bounds.iter().map(|(min, _)| min).sum();
+        let max_sum: BITS = bounds.iter().map(|(_, max)| max).sum();
+        // 丸のセルは矢印の合計が取りうる範囲に絞る。
+        let circle: BITS = cx.entropy_at(&self.circle).to_owned().into();
+        let circle = circle & range(min_sum, max_sum);
+        cx.superimpose(&self.circle, Entropy::try_from(circle).unwrap())?;
+        let (circle_min, circle_max) = (
+            circle.trailing_zeros(),
+            BITS::BITS - 1 - circle.leading_zeros(),
+        );
+        // 矢印上の各セルは、他のセルの合計と合わせて丸のセルの範囲に収まる値に絞る。
+        for (place, (min, max)) in self.cells.iter().zip(bounds) {
+            let others_min = min_sum - min;
+            let others_max = max_sum - max;
+            let bits = range(
+                circle_min.saturating_sub(others_max),
+                circle_max.saturating_sub(others_min),
+            );
+            let entropy = Entropy::try_from(bits & BITS::from(Entropy::<S>::new())).unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::solve_first;
+    #[test]
+    fn disable_around() {
+        let mut field = EntropyField::new();
+        field.add_constraint(AntiKnight).unwrap();
+        field.add_constraint(AntiKing).unwrap();
+        field
+            .insert(Place::new(4, 4).unwrap(), Value::FIVE)
+            .unwrap();
+        for (x, y) in [(6, 5), (2, 3), (5, 6), (3, 2)] {
+            let entropy = field.entropy_at(&Place::new(x, y).unwrap());
+            assert!(!entropy.is_possible(&Value::FIVE));
+        }
+        assert!(
+            field
+                .entropy_at(&Place::new(6, 6).unwrap())
+                .is_possible(&Value::FIVE)
+        );
+
+        // (4, 4)と(6, 3)が同時に5へ収束すると、ナイトの動きで競合する。
+        let mut field = EntropyField::new();
+        field.add_constraint(AntiKnight).unwrap();
+        let five_or_seven: Entropy = Entropy::try_from(0b10100000).unwrap();
+        for (x, y) in [(4, 4), (6, 3)] {
+            field
+                .insert(Place::new(x, y).unwrap(), five_or_seven.clone())
+                .unwrap();
+        }
+        let error = field
+            .insert(Place::new(4, 3).unwrap(), Value::SEVEN)
+            .unwrap_err();
+        assert_eq!(error.rule().unwrap().to_string(), "アンチナイト");
+    }
+    #[test]
+    fn solve() {
+        let mut field: EntropyField = EntropyField::new();
+        field.add_constraint(AntiKnight).unwrap();
+        let found = solve_first(field);
+        for i in 0..Standard::CELLS {
+            let place = Place::new_from_raw(i).unwrap();
+            for knight in place.knight_moves() {
+                assert_ne!(found.entropy_at(&place), found.entropy_at(&knight));
+            }
+        }
+    }
+}
+
+use super::*;
+
+/// チェスのナイトの動きで移動できるセルには、同じ値が入らないルールです。
+#[derive(Debug, Clone, Default)]
+pub struct AntiKnight;
+
+/// チェスのキングの動きで移動できるセルには、同じ値が入らないルールです。
+#[derive(Debug, Clone, Default)]
+pub struct AntiKing;
+
+impl std::fmt::Display for AntiKnight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "アンチナイト")
+    }
+}
+
+impl std::fmt::Display for AntiKing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "アンチキング")
+    }
+}
+
+/// 値が収束したセルから、周囲のセルへその値の否定を伝播します。
+fn disable_around<S: Size>(
+    cx: &mut Propagator<'_, S>,
+    place: &Place<S>,
+    around: Neighbors<S>,
+) -> Result<(), RuleViolationError<S>> {
+    if let Ok(value) = cx.entropy_at(place).to_owned().try_into() {
+        let value: Value<S> = value;
+        for neighbor in around {
+            cx.disable(&neighbor, &value)?;
+        }
+    }
+    Ok(())
+}
+
+impl<S: Size> Constraint<S> for AntiKnight {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        disable_around(cx, place, place.knight_moves())
+    }
+}
+
+impl<S: Size> Constraint<S> for AntiKing {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        disable_around(cx, place, place.king_moves())
+    }
+}
@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::places;
+    fn edge(a: (usize, usize), b: (usize, usize), mark: Mark) -> Edge {
+        let [a, b] = places(&[a, b]).try_into().unwrap();
+        Edge::new(a, b, mark).unwrap()
+    }
+    #[test]
+    fn marks() {
+        let mut field = EntropyField::new();
+        field
+            .add_constraint(edge((0, 0), (1, 0), Mark::White))
+            .unwrap();
+        field
+            .add_constraint(edge((3, 4), (4, 4), Mark::Black))
+            .unwrap();
+        field.add_constraint(edge((6, 0), (6, 1), Mark::X)).unwrap();
+        field.add_constraint(edge((7, 6), (7, 7), Mark::V)).unwrap();
+        // 9の半分や2倍は入らないので、黒丸の両側に9は入らない。
+        let black = field.entropy_at(&Place::new(4, 4).unwrap());
+        assert!(!black.is_possible(&Value::NINE));
+        assert_eq!(field.entropy_at(&Place::new(7, 7).unwrap()).len(), 4);
+        for ((x, y), value) in [
+            ((0, 0), Value::FIVE),
+            ((3, 4), Value::THREE),
+            ((6, 0), Value::TWO),
+            ((7, 6), Value::ONE),
+        ] {
+            field.insert(Place::new(x, y).unwrap(), value).unwrap();
+        }
+        let white = Entropy::try_from(0b1010000).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(1, 0).unwrap()), &white);
+        for ((x, y), value) in [
+            ((4, 4), Value::SIX),
+            ((6, 1), Value::EIGHT),
+            ((7, 7), Value::FOUR),
+        ] {
+            let entropy = field.entropy_at(&Place::new(x, y).unwrap());
+            assert_eq!(entropy, &value.into());
+        }
+        let diagonal = Edge::<Standard>::new(
+            Place::new(0, 0).unwrap(),
+            Place::new(1, 1).unwrap(),
+            Mark::X,
+        );
+        assert!(diagonal.is_none());
+    }
+    #[test]
+    fn negative() {
+        let mut field = EntropyField::new();
+        let marked = edge((4, 4), (5, 4), Mark::White);
+        field
+            .add_constraint(NegativeEdges::new(
+                vec![Mark::White, Mark::Black],
+                [marked.cells()],
+            ))
+            .unwrap();
+        field.add_constraint(marked).unwrap();
+        field
+            .insert(Place::new(4, 4).unwrap(), Value::FOUR)
+            .unwrap();
+        // 印のない辺では連続する値や2倍の値は入らない。
+        let below = field.entropy_at(&Place::new(4, 5).unwrap());
+        for value in [Value::TWO, Value::THREE, Value::FIVE, Value::EIGHT] {
+            assert!(!below.is_possible(&value));
+        }
+        assert!(below.is_possible(&Value::SIX));
+        // 印のある辺は対象外となる。
+        let right = field.entropy_at(&Place::new(5, 4).unwrap());
+        assert_eq!(right, &Entropy::try_from(0b101000).unwrap());
+    }
+}
+
+use super::*;
+
+/// 縦横に隣接する2つのセルの間に付けられる印です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// 白丸: 2つの値が連続する。
+    White,
+    /// 黒丸: 一方の値がもう一方の2倍となる。
+    Black,
+    /// X: 2つの値の合計が10となる。
+    X,
+    /// V: 2つの値の合計が5となる。
+    V,
+}
+
+impl Mark {
+    /// 2つの値がこの印の関係を満たすかどうかを返します。
+    pub fn holds(self, a: BITS, b: BITS) -> bool {
+        match self {
+            Mark::White => a.abs_diff(b) == 1,
+            Mark::Black => a == b * 2 || b == a * 2,
+            Mark::X => a + b == 10,
+            Mark::V => a + b == 5,
+        }
+    }
+}
+
+impl std::fmt::Display for Mark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mark::White => write!(f, "白丸"),
+            Mark::Black => write!(f, "黒丸"),
+            Mark::X => write!(f, "X"),
+            Mark::V => write!(f, "V"),
+        }
+    }
+}
+
+/// toのエントロピーを、fromに残っているいずれかの値とrelationを満たす値に絞ります。
+pub(super) fn filter<S: Size>(
+    cx: &mut Propagator<'_, S>,
+    from: &Place<S>,
+    to: &Place<S>,
+    relation: impl Fn(BITS, BITS) -> bool,
+) -> Result<(), RuleViolationError<S>> {
+    let partners: Vec<BITS> = cx
+        .entropy_at(from)
+        .to_owned()
+        .into_iter()
+        .map(BITS::from)
+        .collect();
+    let mut supported: BITS = 0;
+    for value in cx.entropy_at(to).to_owned() {
+        let value = BITS::from(value);
+        if partners.iter().any(|&partner| relation(partner, value)) {
+            supported |= 1 << value;
+        }
+    }
+    let entropy: Entropy<S> = supported.try_into().unwrap();
+    cx.superimpose(to, entropy)?;
+    Ok(())
+}
+
+/// 縦横に隣接する2つのセルの間の印による制約です。
+#[derive(Debug, Clone)]
+pub struct Edge<S: Size = Standard> {
+    a: Place<S>,
+    b: Place<S>,
+    mark: Mark,
+}
+
+impl<S: Size> Edge<S> {
+    /// 新しいEdgeを返します。
+    /// 2つのセルが縦横に隣接していない場合はNoneが返ります。
+    pub fn new(a: Place<S>, b: Place<S>, mark: Mark) -> Option<Self> {
+        if a.orthogonal_neighbors().any(|neighbor| neighbor == b) {
+            Some(Edge { a, b, mark })
+        } else {
+            None
+        }
+    }
+    /// 印の両側のセルを返します。
+    pub fn cells(&self) -> (&Place<S>, &Place<S>) {
+        (&self.a, &self.b)
+    }
+    /// 印の種類を返します。
+    pub fn mark(&self) -> Mark {
+        self.mark
+    }
+}
+
+impl<S: Size> std::fmt::Display for Edge<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} - {}", self.mark, self.a, self.b)
+    }
+}
+
+impl<S: Size> Constraint<S> for Edge<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        filter(cx, &self.a, &self.b, |a, b| self.mark.holds(a, b))?;
+        filter(cx, &self.b, &self.a, |b, a| self.mark.holds(a, b))
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if place == &self.a {
+            filter(cx, &self.a, &self.b, |a, b| self.mark.holds(a, b))
+        } else if place == &self.b {
+            filter(cx, &self.b, &self.a, |b, a| self.mark.holds(a, b))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// 印のない辺についての否定の制約です。
+/// 印の付けられていない縦横に隣接する2つのセルは、
+/// 指定された種類のいずれの印の関係も満たしません。
+#[derive(Debug, Clone)]
+pub struct NegativeEdges<S: Size = Standard> {
+    /// 否定する印の種類
+    marks: Vec<Mark>,
+    /// 印の付けられている辺
+    marked: Vec<(Place<S>, Place<S>)>,
+}
+
+impl<S: Size> NegativeEdges<S> {
+    /// 否定する印の種類と、印の付けられている辺から新しいNegativeEdgesを返します。
+    pub fn new<'a>(
+        marks: Vec<Mark>,
+        marked: impl IntoIterator<Item = (&'a Place<S>, &'a Place<S>)>,
+    ) -> Self {
+        let marked = marked
+            .into_iter()
+            .map(|(a, b)| (a.to_owned(), b.to_owned()))
+            .collect();
+        NegativeEdges { marks, marked }
+    }
+    /// 2つのセルの間に印が付けられているかどうかを返します。
+    fn is_marked(&self, a: &Place<S>, b: &Place<S>) -> bool {
+        self.marked
+            .iter()
+            .any(|(c, d)| (a, b) == (c, d) || (a, b) == (d, c))
+    }
+}
+
+impl<S: Size> std::fmt::Display for NegativeEdges<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "印のない辺の否定:")?;
+        for mark in &self.marks {
+            write!(f, " {mark}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> Constraint<S> for NegativeEdges<S> {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        for neighbor in place.orthogonal_neighbors() {
+            if !self.is_marked(place, &neighbor) {
+                filter(cx, place, &neighbor, |a, b| {
+                    !self.marks.iter().any(|mark| mark.holds(a, b))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
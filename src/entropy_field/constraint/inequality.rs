@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::{places, solve_first};
+    fn inequality(smaller: (usize, usize), larger: (usize, usize)) -> Inequality {
+        let [smaller, larger] = places(&[smaller, larger]).try_into().unwrap();
+        Inequality::new(smaller, larger).unwrap()
+    }
+    #[test]
+    fn chain() {
+        let mut field = EntropyField::new();
+        field.add_constraint(inequality((0, 0), (1, 0))).unwrap();
+        field.add_constraint(inequality((1, 0), (2, 0))).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(1, 0).unwrap()).len(), 7);
+        field
+            .insert(Place::new(2, 0).unwrap(), Value::THREE)
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(0, 0).unwrap()),
+            &Value::ONE.into()
+        );
+        assert_eq!(
+            field.entropy_at(&Place::new(1, 0).unwrap()),
+            &Value::TWO.into()
+        );
+        // 同じ行の1と2は既に使われているので、3より小さい値は残らない。
+        let error = field
+            .add_constraint(inequality((3, 0), (2, 0)))
+            .unwrap_err();
+        assert_eq!(error.rule().unwrap().to_string(), "不等号: (3, 0) < (2, 0)");
+        assert!(
+            Inequality::<Standard>::new(Place::new(0, 0).unwrap(), Place::new(2, 0).unwrap())
+                .is_none()
+        );
+    }
+    #[test]
+    fn futoshiki() {
+        type Size = Latin<4>;
+        let mut field = EntropyField::<Size>::new();
+        let inequalities = [
+            ((0, 0), (1, 0)),
+            ((2, 1), (2, 0)),
+            ((1, 2), (1, 1)),
+            ((3, 2), (3, 3)),
+            ((0, 3), (0, 2)),
+        ];
+        for &((sx, sy), (lx, ly)) in &inequalities {
+            let smaller = Place::new(sx, sy).unwrap();
+            let larger = Place::new(lx, ly).unwrap();
+            field
+                .add_constraint(Inequality::<Size>::new(smaller, larger).unwrap())
+                .unwrap();
+        }
+        let found = solve_first(field);
+        let value = |(x, y)| BITS::from(found.entropy_at(&Place::new(x, y).unwrap()).to_owned());
+        for (smaller, larger) in inequalities {
+            assert!(value(smaller) < value(larger));
+        }
+    }
+}
+
+use super::edge::filter;
+use super::*;
+
+/// 不等号数独の、縦横に隣接する2つのセルの間の不等号です。
+/// 小さい側のセルの値は、大きい側のセルの値より小さくなります。
+/// 相手のセルに残っているいずれの値とも大小関係を満たさない値を取り除きます。
+#[derive(Debug, Clone)]
+pub struct Inequality<S: Size = Standard> {
+    smaller: Place<S>,
+    larger: Place<S>,
+}
+
+impl<S: Size> Inequality<S> {
+    /// 小さい側のセルと大きい側のセルから新しいInequalityを返します。
+    /// 2つのセルが縦横に隣接していない場合はNoneが返ります。
+    pub fn new(smaller: Place<S>, larger: Place<S>) -> Option<Self> {
+        if smaller
+            .orthogonal_neighbors()
+            .any(|neighbor| neighbor == larger)
+        {
+            Some(Inequality { smaller, larger })
+        } else {
+            None
+        }
+    }
+    /// 小さい側のセルを返します。
+    pub fn smaller(&self) -> &Place<S> {
+        &self.smaller
+    }
+    /// 大きい側のセルを返します。
+    pub fn larger(&self) -> &Place<S> {
+        &self.larger
+    }
+}
+
+impl<S: Size> std::fmt::Display for Inequality<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "不等号: {} < {}", self.smaller, self.larger)
+    }
+}
+
+impl<S: Size> Constraint<S> for Inequality<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        filter(cx, &self.smaller, &self.larger, |a, b| a < b)?;
+        filter(cx, &self.larger, &self.smaller, |b, a| a < b)
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if place == &self.smaller {
+            filter(cx, &self.smaller, &self.larger, |a, b| a < b)
+        } else if place == &self.larger {
+            filter(cx, &self.larger, &self.smaller, |b, a| a < b)
+        } else {
+            Ok(())
+        }
+    }
+}
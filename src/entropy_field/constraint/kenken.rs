@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::places;
+    type Size = Latin<4>;
+    fn cage(cells: &[(usize, usize)], operation: Operation, target: BITS) -> KenKen<Size> {
+        KenKen::new(places(cells), operation, target).unwrap()
+    }
+    #[test]
+    fn solve() {
+        use Operation::*;
+        let mut field = EntropyField::<Size>::new();
+        for cage in [
+            cage(&[(0, 0), (0, 1)], Divide, 3),
+            cage(&[(1, 0), (2, 0)], Subtract, 1),
+            cage(&[(3, 0), (3, 1)], Divide, 2),
+            cage(&[(1, 1), (2, 1)], Divide, 4),
+            cage(&[(0, 2), (0, 3)], Divide, 2),
+            cage(&[(1, 2), (1, 3), (2, 3)], Multiply, 6),
+            cage(&[(2, 2), (3, 2)], Divide, 4),
+            cage(&[(3, 3)], Add, 3),
+        ] {
+            field.add_constraint(cage).unwrap();
+        }
+        // 商が3となる組は1と3のみとなる。
+        let entropy = field.entropy_at(&Place::new(0, 1).unwrap());
+        for value in [2, 4] {
+            assert!(!entropy.is_possible(&Value::new(value).unwrap()));
+        }
+        // このケージの組では、伝播のみで全てのセルが収束する。
+        assert_eq!(
+            field.to_string(),
+            " 1  2  3  4 \n 3  4  1  2 \n 2  3  4  1 \n 4  1  2  3 "
+        );
+        assert!(KenKen::<Size>::new(vec![Place::new(0, 0).unwrap()], Subtract, 1).is_none());
+    }
+}
+
+use super::*;
+
+/// ケンケンのケージの演算です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// 値の和
+    Add,
+    /// 2つの値の差
+    Subtract,
+    /// 値の積
+    Multiply,
+    /// 2つの値の商
+    Divide,
+}
+
+impl Operation {
+    /// 値の組に演算を適用した結果が指定された値となるかどうかを返します。
+    /// 差と商は大きい値から小さい値への演算とします。
+    pub fn holds(self, values: &[BITS], target: BITS) -> bool {
+        match self {
+            Operation::Add => values.iter().sum::<BITS>() == target,
+            Operation::Multiply => values.iter().product::<BITS>() == target,
+            Operation::Subtract => values[0].abs_diff(values[1]) == target,
+            Operation::Divide => {
+                let (large, small) = (values[0].max(values[1]), values[0].min(values[1]));
+                large == small * target
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Add => write!(f, "+"),
+            Operation::Subtract => write!(f, "−"),
+            Operation::Multiply => write!(f, "×"),
+            Operation::Divide => write!(f, "÷"),
+        }
+    }
+}
+
+/// ケンケンのケージです。
+/// ケージ内のセルの値に演算を適用した結果が指定された値となります。
+/// 行と列が異なれば、ケージ内で同じ値が繰り返されてもかまいません。
+/// 各セルの現在の可能性から全ての割り当てを調べ、いずれかで成り立つ値のみを残します。
+#[derive(Debug, Clone)]
+pub struct KenKen<S: Size = Standard> {
+    cells: Vec<Place<S>>,
+    operation: Operation,
+    target: BITS,
+}
+
+impl<S: Size> KenKen<S> {
+    /// 新しいKenKenを返します。
+    /// セルが空である、重複している、または差や商のケージのセルが2つでない場合はNoneが返ります。
+    pub fn new(cells: Vec<Place<S>>, operation: Operation, target: BITS) -> Option<Self> {
+        if !is_path(&cells) {
+            return None;
+        }
+        if matches!(operation, Operation::Subtract | Operation::Divide) && cells.len() != 2 {
+            return None;
+        }
+        Some(KenKen {
+            cells,
+            operation,
+            target,
+        })
+    }
+    /// ケージ内のセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+    /// ケージの演算を返します。
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+    /// 演算の結果を返します。
+    pub fn target(&self) -> BITS {
+        self.target
+    }
+
+    /// 先頭から順に値を割り当て、演算が成り立つ割り当てに現れた値をsupportedに加えます。
+    fn search(&self, candidates: &[BITS], values: &mut Vec<BITS>, supported: &mut [BITS]) {
+        let i = values.len();
+        if i == candidates.len() {
+            if self.operation.holds(values, self.target) {
+                for (supported, value) in supported.iter_mut().zip(values.iter()) {
+                    *supported |= 1 << value;
+                }
+            }
+            return;
+        }
+        for value in 1..BITS::BITS {
+            if candidates[i] & (1 << value) == 0 {
+                continue;
+            }
+            // 同じ行や列のセルには同じ値が入らない。
+            let place = &self.cells[i];
+            let conflicts = self.cells[..i]
+                .iter()
+                .zip(values.iter())
+                .any(|(other, &v)| {
+                    v == value && (other.x() == place.x() || other.y() == place.y())
+                });
+            if conflicts {
+                continue;
+            }
+            values.push(value);
+            let partial = &values[..];
+            // 和と積は途中で目標を超えた場合に打ち切る。
+            let hopeless = match self.operation {
+                Operation::Add => partial.iter().sum::<BITS>() > self.target,
+                Operation::Multiply => partial
+                    .iter()
+                    .try_fold(1 as BITS, |product, value| product.checked_mul(*value))
+                    .is_none_or(|product| !self.target.is_multiple_of(product)),
+                _ => false,
+            };
+            if !hopeless {
+                self.search(candidates, values, supported);
+            }
+            values.pop();
+        }
+    }
+}
+
+impl<S: Size> std::fmt::Display for KenKen<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ケンケン({}{}):", self.target, self.operation)?;
+        for place in &self.cells {
+            write!(f, " {place}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> Constraint<S> for KenKen<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let candidates: Vec<BITS> = self
+            .cells
+            .iter()
+            .map(|place| cx.entropy_at(place).to_owned().into())
+            .collect();
+        let mut supported = vec![0; candidates.len()];
+        self.search(&candidates, &mut Vec::new(), &mut supported);
+        for (place, supported) in self.cells.iter().zip(supported) {
+            let entropy: Entropy<S> = supported.try_into().unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::{places, solve_first};
+    fn cage(cells: &[(usize, usize)], sum: BITS) -> Cage {
+        Cage::new(places(cells), sum).unwrap()
+    }
+    #[test]
+    fn combinations() {
+        let mut field = EntropyField::new();
+        field.add_constraint(cage(&[(0, 0), (1, 0)], 3)).unwrap();
+        field
+            .add_constraint(cage(&[(0, 1), (1, 1), (2, 1)], 24))
+            .unwrap();
+        let twelve: Entropy = Entropy::try_from(0b110).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()), &twelve);
+        assert_eq!(field.entropy_at(&Place::new(1, 1).unwrap()).len(), 3);
+        // (0, 0)が1ならば(1, 0)は2となる。
+        field.insert(Place::new(0, 0).unwrap(), Value::ONE).unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(1, 0).unwrap()),
+            &Value::TWO.into()
+        );
+        let error = field
+            .add_constraint(cage(&[(2, 0), (3, 0)], 4))
+            .unwrap_err();
+        assert!(error.rule().is_some());
+    }
+    #[test]
+    fn little_killer() {
+        let mut field: EntropyField = EntropyField::new();
+        let clue = LittleKiller::new(Place::new(8, 2).unwrap(), Slant::UpLeft, 24).unwrap();
+        let cells: Vec<_> = clue.cells().iter().map(|p| (p.x(), p.y())).collect();
+        assert_eq!(cells, [(8, 2), (7, 1), (6, 0)]);
+        field.add_constraint(clue).unwrap();
+        let high = Entropy::try_from(0b1111000000).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(7, 1).unwrap()), &high);
+        // 4つのセルの合計が5ならば、各セルは1か2となる。
+        let clue = LittleKiller::new(Place::new(0, 5).unwrap(), Slant::DownRight, 5).unwrap();
+        field.add_constraint(clue).unwrap();
+        let low = Entropy::try_from(0b110).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(3, 8).unwrap()), &low);
+        // 3つのセルの合計は3以上となる。
+        let clue = LittleKiller::new(Place::new(0, 6).unwrap(), Slant::DownRight, 2).unwrap();
+        let error = field.add_constraint(clue).unwrap_err();
+        assert_eq!(
+            error.rule().unwrap().to_string(),
+            "リトルキラー(合計2): (0, 6)から右下"
+        );
+        assert!(
+            LittleKiller::<Standard>::new(Place::new(1, 1).unwrap(), Slant::DownRight, 3).is_none()
+        );
+    }
+    #[test]
+    fn solve() {
+        const SOLUTION: &str = "\
+            639251748458367912172849365967435281824176593315928476796583124541692837283714659";
+        let value = |x: usize, y: usize| (SOLUTION.as_bytes()[y * 9 + x] - b'0') as BITS;
+        let mut field = EntropyField::new();
+        let mut cages = Vec::new();
+        for y in 0..9 {
+            for x in (0..9).step_by(2) {
+                let cells: Vec<_> = (x..(x + 2).min(9)).map(|x| (x, y)).collect();
+                let sum = cells.iter().map(|&(x, y)| value(x, y)).sum();
+                cages.push((cells.clone(), sum));
+                field.add_constraint(cage(&cells, sum)).unwrap();
+            }
+        }
+        let found = solve_first(field);
+        for (cells, sum) in cages {
+            let total: BITS = cells
+                .iter()
+                .map(|&(x, y)| {
+                    let value: Value = found
+                        .entropy_at(&Place::new(x, y).unwrap())
+                        .to_owned()
+                        .try_into()
+                        .unwrap();
+                    BITS::from(value)
+                })
+                .sum();
+            assert_eq!(total, sum);
+        }
+    }
+}
+
+use super::arrow::range;
+use super::*;
+use std::collections::HashSet;
+
+/// キラー数独のケージです。
+/// ケージ内のセルには重複のない値が入り、その合計が指定された値となります。
+/// 他のセルの現在のエントロピーの下で、合計を満たす組み合わせのいずれかに
+/// 現れる値のみが各セルに残るよう伝播します。
+#[derive(Debug, Clone)]
+pub struct Cage<S: Size = Standard> {
+    cells: Vec<Place<S>>,
+    sum: BITS,
+}
+
+impl<S: Size> Cage<S> {
+    /// 新しいCageを返します。
+    /// セルが空である、重複している、または盤面の一辺のセル数より多い場合はNoneが返ります。
+    pub fn new(cells: Vec<Place<S>>, sum: BITS) -> Option<Self> {
+        (is_path(&cells) && cells.len() <= S::SIDE).then_some(Cage { cells, sum })
+    }
+    /// ケージ内のセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+    /// ケージ内の値の合計を返します。
+    pub fn sum(&self) -> BITS {
+        self.sum
+    }
+}
+
+/// 値の集合を表すビット列に含まれる値の合計を返します。
+fn sum_of(mut bits: BITS) -> BITS {
+    let mut sum = 0;
+    while bits != 0 {
+        sum += bits.trailing_zeros();
+        bits &= bits - 1;
+    }
+    sum
+}
+
+/// 各セルに入り得る値の集合から、重複のない組み合わせで合計がsumとなるものを探し、
+/// 各セルについていずれかの組み合わせに現れる値の集合を返します。
+/// 組み合わせが1つもない場合は全て空の集合となります。
+pub(crate) fn supported_values(candidates: &[BITS], sum: BITS) -> Vec<BITS> {
+    // layers[i]は先頭からi個のセルに使った値の集合のうち、合計がsumを超えないもの
+    let mut layers: Vec<HashSet<BITS>> = vec![HashSet::from([0])];
+    for &candidate in candidates {
+        let mut next = HashSet::new();
+        for &used in layers.last().unwrap() {
+            let mut rest = candidate & !used;
+            while rest != 0 {
+                let bit = rest & rest.wrapping_neg();
+                rest -= bit;
+                if sum_of(used) + bit.trailing_zeros() <= sum {
+                    next.insert(used | bit);
+                }
+            }
+        }
+        layers.push(next);
+    }
+    // 末尾から、合計がsumとなる組み合わせに繋がる集合だけを残していく。
+    let mut supported = vec![0; candidates.len()];
+    let mut alive: HashSet<BITS> = layers
+        .pop()
+        .unwrap()
+        .into_iter()
+        .filter(|&used| sum_of(used) == sum)
+        .collect();
+    for (i, layer) in layers.into_iter().enumerate().rev() {
+        let mut previous = HashSet::new();
+        for used in layer {
+            let mut rest = candidates[i] & !used;
+            while rest != 0 {
+                let bit = rest & rest.wrapping_neg();
+                rest -= bit;
+                if alive.contains(&(used | bit)) {
+                    supported[i] |= bit;
+                    previous.insert(used);
+                }
+            }
+        }
+        alive = previous;
+    }
+    supported
+}
+
+impl<S: Size> std::fmt::Display for Cage<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ケージ(合計{}):", self.sum)?;
+        for place in &self.cells {
+            write!(f, " {place}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> Constraint<S> for Cage<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let candidates: Vec<BITS> = self
+            .cells
+            .iter()
+            .map(|place| cx.entropy_at(place).to_owned().into())
+            .collect();
+        let supported = supported_values(&candidates, self.sum);
+        for (place, supported) in self.cells.iter().zip(supported) {
+            let entropy: Entropy<S> = supported.try_into().unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
+
+/// リトルキラー数独の、盤面の外から斜めに向けられた手がかりです。
+/// 盤面の端のセルから斜めに進んだセルの値の合計が指定された値となります。
+/// ケージと異なり、同じ値が繰り返し現れてもかまいません。
+/// 他のセルの最小値と最大値の合計から、各セルの値の範囲を絞り込みます。
+#[derive(Debug, Clone)]
+pub struct LittleKiller<S: Size = Standard> {
+    direction: Slant,
+    /// 端のセルから順に並べた斜めのセル
+    cells: Vec<Place<S>>,
+    sum: BITS,
+}
+
+impl<S: Size> LittleKiller<S> {
+    /// 斜めの列が始まる端のセルと、進む向き、合計から新しいLittleKillerを返します。
+    /// 端のセルから向きと逆に進んだ位置が盤面の外でない場合はNoneが返ります。
+    pub fn new(start: Place<S>, direction: Slant, sum: BITS) -> Option<Self> {
+        let (dx, dy) = direction.offset();
+        let previous = (
+            start.x().checked_add_signed(-dx),
+            start.y().checked_add_signed(-dy),
+        );
+        if let (Some(x), Some(y)) = previous
+            && Place::<S>::new(x, y).is_some()
+        {
+            return None;
+        }
+        Some(LittleKiller {
+            direction,
+            cells: start.slant(direction).into_iter().collect(),
+            sum,
+        })
+    }
+    /// 端のセルから順に並べた斜めのセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+    /// 斜めに進む向きを返します。
+    pub fn direction(&self) -> Slant {
+        self.direction
+    }
+    /// 斜めのセルの値の合計を返します。
+    pub fn sum(&self) -> BITS {
+        self.sum
+    }
+}
+
+impl<S: Size> std::fmt::Display for LittleKiller<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "リトルキラー(合計{}): {}から{}",
+            self.sum, self.cells[0], self.direction
+        )
+    }
+}
+
+impl<S: Size> Constraint<S> for LittleKiller<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let bounds: Vec<(BITS, BITS)> = self
+            .cells
+            .iter()
+            .map(|place| {
+                let bits: BITS = cx.entropy_at(place).to_owned().into();
+                (bits.trailing_zeros(), BITS::BITS - 1 - bits.leading_zeros())
+            })
+            .collect();
+        let min_sum: BITS = bounds.iter().map(|(min, _)| min).sum();
+        let max_sum: BITS = bounds.iter().map(|(_, max)| max).sum();
+        for (place, (min, max)) in self.cells.iter().zip(bounds) {
+            // 他のセルの合計と合わせてsumとなる値の範囲に絞る。
+            let bits = match self.sum.checked_sub(min_sum - min) {
+                Some(upper) => range(self.sum.saturating_sub(max_sum - max), upper),
+                None => 0,
+            };
+            let entropy = Entropy::try_from(bits & BITS::from(Entropy::<S>::new())).unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::places;
+    #[test]
+    fn whisper() {
+        let mut field = EntropyField::new();
+        field
+            .add_constraint(Whisper::german(places(&[(0, 0), (1, 1), (2, 0)])).unwrap())
+            .unwrap();
+        // 5はどの値とも5以上離れない。
+        let middle = field.entropy_at(&Place::new(1, 1).unwrap());
+        assert!(!middle.is_possible(&Value::FIVE));
+        field
+            .insert(Place::new(1, 1).unwrap(), Value::THREE)
+            .unwrap();
+        let end = Entropy::try_from(0b1100000000).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()), &end);
+        assert_eq!(field.entropy_at(&Place::new(2, 0).unwrap()), &end);
+        field
+            .insert(Place::new(0, 0).unwrap(), Value::NINE)
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(2, 0).unwrap()),
+            &Value::EIGHT.into()
+        );
+        // 6の両隣は1のみとなり、同じ行に1が2つ入ってしまう。
+        let error = field
+            .add_constraint(Whisper::german(places(&[(3, 3), (4, 3), (5, 3)])).unwrap())
+            .and_then(|_| field.insert(Place::new(4, 3).unwrap(), Value::SIX))
+            .unwrap_err();
+        assert!(error.rule().is_some());
+    }
+    #[test]
+    fn renban() {
+        let mut field = EntropyField::new();
+        let line = places(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+        field.add_constraint(Renban::new(line).unwrap()).unwrap();
+        field.insert(Place::new(1, 0).unwrap(), Value::TWO).unwrap();
+        field
+            .insert(Place::new(2, 0).unwrap(), Value::FIVE)
+            .unwrap();
+        // 2と5を含む4つの連続した値は2から5のみとなる。
+        let rest = Entropy::try_from(0b11000).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()), &rest);
+        assert_eq!(field.entropy_at(&Place::new(3, 0).unwrap()), &rest);
+
+        let mut field = EntropyField::new();
+        field.insert(Place::new(0, 1).unwrap(), Value::ONE).unwrap();
+        field
+            .insert(Place::new(2, 3).unwrap(), Value::FOUR)
+            .unwrap();
+        // 1と4は3つの連続した値に含まれない。
+        let error = field
+            .add_constraint(Renban::new(places(&[(0, 1), (1, 2), (2, 3)])).unwrap())
+            .unwrap_err();
+        assert_eq!(
+            error.rule().unwrap().to_string(),
+            "レンバン: (0, 1) - (1, 2) - (2, 3)"
+        );
+    }
+    #[test]
+    fn palindrome() {
+        let mut field = EntropyField::new();
+        let line = places(&[(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+        field
+            .add_constraint(Palindrome::new(line).unwrap())
+            .unwrap();
+        field
+            .insert(Place::new(1, 1).unwrap(), Value::SEVEN)
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(3, 3).unwrap()),
+            &Value::SEVEN.into()
+        );
+        // 中央のセルには対になるセルがない。
+        assert_eq!(field.entropy_at(&Place::new(2, 2).unwrap()).len(), 8);
+        // 同じ行で対になるセルには、同じ値が入らない。
+        field.insert(Place::new(5, 5).unwrap(), Value::ONE).unwrap();
+        let error = field
+            .add_constraint(Palindrome::new(places(&[(5, 5), (6, 6), (7, 5)])).unwrap())
+            .unwrap_err();
+        assert!(error.rule().is_some());
+        assert!(Palindrome::<Standard>::new(places(&[(0, 0), (0, 0)])).is_none());
+    }
+}
+
+use super::edge::filter;
+use super::killer::supported_values;
+use super::*;
+
+/// セルの列を端から順に表示します。
+fn write_path<S: Size>(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    cells: &[Place<S>],
+) -> std::fmt::Result {
+    write!(f, "{name}: {}", cells[0])?;
+    for place in &cells[1..] {
+        write!(f, " - {place}")?;
+    }
+    Ok(())
+}
+
+/// ウィスパーラインです。
+/// 線上で隣りあうセルの値の差が、指定された値以上となります。
+#[derive(Debug, Clone)]
+pub struct Whisper<S: Size = Standard> {
+    cells: Vec<Place<S>>,
+    difference: BITS,
+}
+
+impl<S: Size> Whisper<S> {
+    /// 端から順に並べたセルと、隣りあうセルの値の差の下限から新しいWhisperを返します。
+    /// セルが空である、または重複している場合はNoneが返ります。
+    pub fn new(cells: Vec<Place<S>>, difference: BITS) -> Option<Self> {
+        is_path(&cells).then_some(Whisper { cells, difference })
+    }
+    /// 隣りあうセルの値の差が5以上となる、ジャーマンウィスパーを返します。
+    pub fn german(cells: Vec<Place<S>>) -> Option<Self> {
+        Whisper::new(cells, 5)
+    }
+    /// 端から順に並べたセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+    /// 隣りあうセルの値の差の下限を返します。
+    pub fn difference(&self) -> BITS {
+        self.difference
+    }
+}
+
+impl<S: Size> std::fmt::Display for Whisper<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_path(
+            f,
+            &format!("ウィスパー(差{}以上)", self.difference),
+            &self.cells,
+        )
+    }
+}
+
+impl<S: Size> Constraint<S> for Whisper<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        for place in &self.cells {
+            self.on_shrink(cx, place, Entropy::new().disabled_values())?;
+        }
+        Ok(())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        let Some(i) = self.cells.iter().position(|cell| cell == place) else {
+            return Ok(());
+        };
+        let neighbors = [i.checked_sub(1), Some(i + 1)];
+        for neighbor in neighbors.into_iter().flatten() {
+            if let Some(neighbor) = self.cells.get(neighbor) {
+                filter(cx, place, neighbor, |a, b| a.abs_diff(b) >= self.difference)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// レンバンラインです。
+/// 線上のセルには、重複のない連続した値が順不同で入ります。
+/// 取りうる連続した値の範囲ごとに、その範囲の値が全て線上に入る割り当てを調べます。
+#[derive(Debug, Clone)]
+pub struct Renban<S: Size = Standard> {
+    cells: Vec<Place<S>>,
+}
+
+impl<S: Size> Renban<S> {
+    /// 線上のセルから新しいRenbanを返します。
+    /// セルが空である、重複している、または盤面の一辺のセル数より多い場合はNoneが返ります。
+    pub fn new(cells: Vec<Place<S>>) -> Option<Self> {
+        (is_path(&cells) && cells.len() <= S::SIDE).then_some(Renban { cells })
+    }
+    /// 線上のセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+}
+
+impl<S: Size> std::fmt::Display for Renban<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_path(f, "レンバン", &self.cells)
+    }
+}
+
+impl<S: Size> Constraint<S> for Renban<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let len = self.cells.len() as BITS;
+        let candidates: Vec<BITS> = self
+            .cells
+            .iter()
+            .map(|place| cx.entropy_at(place).to_owned().into())
+            .collect();
+        let mut supported = vec![0; candidates.len()];
+        for min in 1..=S::SIDE as BITS + 1 - len {
+            // 範囲内の重複のない値の合計が範囲の合計と等しければ、範囲の値が全て入る。
+            let range = ((1 << len) - 1) << min;
+            let masked: Vec<BITS> = candidates.iter().map(|bits| bits & range).collect();
+            let sum = (min..min + len).sum();
+            for (supported, bits) in supported.iter_mut().zip(supported_values(&masked, sum)) {
+                *supported |= bits;
+            }
+        }
+        for (place, supported) in self.cells.iter().zip(supported) {
+            let entropy: Entropy<S> = supported.try_into().unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
+
+/// パリンドロームラインです。
+/// 線の両端から同じ距離にあるセルには同じ値が入ります。
+#[derive(Debug, Clone)]
+pub struct Palindrome<S: Size = Standard> {
+    cells: Vec<Place<S>>,
+}
+
+impl<S: Size> Palindrome<S> {
+    /// 端から順に並べたセルから新しいPalindromeを返します。
+    /// セルが空である、または重複している場合はNoneが返ります。
+    pub fn new(cells: Vec<Place<S>>) -> Option<Self> {
+        is_path(&cells).then_some(Palindrome { cells })
+    }
+    /// 端から順に並べたセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+}
+
+impl<S: Size> std::fmt::Display for Palindrome<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_path(f, "パリンドローム", &self.cells)
+    }
+}
+
+impl<S: Size> Constraint<S> for Palindrome<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        for place in &self.cells[..self.cells.len() / 2] {
+            self.on_shrink(cx, place, Entropy::new().disabled_values())?;
+        }
+        Ok(())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        let Some(i) = self.cells.iter().position(|cell| cell == place) else {
+            return Ok(());
+        };
+        let mirror = &self.cells[self.cells.len() - 1 - i];
+        if mirror != place {
+            let entropy = cx.entropy_at(place).to_owned();
+            cx.superimpose(mirror, entropy)?;
+            let entropy = cx.entropy_at(mirror).to_owned();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    #[test]
+    fn pointing_and_claiming() {
+        let mut field = EntropyField::new();
+        field.add_constraint(LockedCandidates).unwrap();
+        let without_one = {
+            let mut entropy = Entropy::new();
+            entropy.disable(&Value::ONE).unwrap();
+            entropy
+        };
+        // 左上のボックスで1が1行目にしか入らないと、1行目の他のボックスに1は入らない。
+        for (x, y) in [(0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            field
+                .insert(Place::new(x, y).unwrap(), without_one.clone())
+                .unwrap();
+        }
+        assert!(
+            !field
+                .entropy_at(&Place::new(5, 0).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        assert!(
+            field
+                .entropy_at(&Place::new(5, 1).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        // 5列目で1が中央のボックスにしか入らないと、中央のボックスの他の列に1は入らない。
+        for y in [0, 1, 2, 6, 7, 8] {
+            field
+                .insert(Place::new(4, y).unwrap(), without_one.clone())
+                .unwrap();
+        }
+        assert!(
+            !field
+                .entropy_at(&Place::new(3, 4).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        assert!(
+            field
+                .entropy_at(&Place::new(4, 4).unwrap())
+                .is_possible(&Value::ONE)
+        );
+    }
+    #[test]
+    fn fewer_guesses() {
+        use crate::brute_force::{Attacker, Report};
+        let givens =
+            "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+        let mut field = EntropyField::new();
+        for (i, c) in givens.bytes().enumerate() {
+            if c == b'.' {
+                continue;
+            }
+            let value = Value::new((c - b'0') as u32).unwrap();
+            field
+                .insert(Place::new_from_raw(i).unwrap(), value)
+                .unwrap();
+        }
+        // 最初の解答が見つかるまでに仮定した回数を数える。
+        let guesses = |field: EntropyField| {
+            Attacker::new(field)
+                .take_while(|report| !matches!(report, Report::Found(_)))
+                .filter(|report| matches!(report, Report::Try { .. }))
+                .count()
+        };
+        let plain = guesses(field.clone());
+        field.add_constraint(LockedCandidates).unwrap();
+        let locked = guesses(field);
+        assert!(locked < plain, "{locked} >= {plain}");
+    }
+}
+
+use super::*;
+
+/// ロックされた候補(ポインティングとボックス・ラインの削減)の検出です。
+/// ボックス内である値の入り得るセルが1つの行または列に揃う場合、その行または列の
+/// ボックス外のセルからその値を否定します。
+/// 逆に、行または列である値の入り得るセルが1つのボックスに揃う場合、
+/// そのボックスの行または列の外のセルからその値を否定します。
+/// 不規則な領域が指定されている場合は、ボックスの代わりに領域を用います。
+///
+/// 盤面には自動では追加されないため、用いる場合は`EntropyField::add_constraint`で
+/// 明示的に追加してください。追加した盤面では、Attackerの仮定の回数が減ります。
+#[derive(Debug, Clone, Default)]
+pub struct LockedCandidates;
+
+impl std::fmt::Display for LockedCandidates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ロックされた候補")
+    }
+}
+
+/// ブロック内で指定された値の入り得るセルを返します。
+fn candidates<S: Size>(cx: &Propagator<'_, S>, block: Block<S>, value: &Value<S>) -> Vec<Place<S>> {
+    block
+        .into_iter()
+        .filter(|place| cx.entropy_at(place).is_possible(value))
+        .collect()
+}
+
+/// targetのセルのうち、候補のセルに含まれないものから値を否定します。
+fn eliminate<S: Size>(
+    cx: &mut Propagator<'_, S>,
+    cells: &[Place<S>],
+    target: Block<S>,
+    value: &Value<S>,
+) -> Result<(), RuleViolationError<S>> {
+    for place in target {
+        if !cells.contains(&place) {
+            cx.disable(&place, value)?;
+        }
+    }
+    Ok(())
+}
+
+impl<S: Size> Constraint<S> for LockedCandidates {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        disabled: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !cx.layout().boxes() {
+            return Ok(());
+        }
+        let dependencies = cx.dependencies(place);
+        for value in disabled {
+            // ポインティング: ボックス内の候補が1つの行または列に揃う。
+            let cells = candidates(cx, dependencies.region(), &value);
+            if cells.len() > 1 {
+                let deps = cx.dependencies(&cells[0]);
+                if cells.iter().all(|cell| cell.y() == cells[0].y()) {
+                    eliminate(cx, &cells, deps.x_line(), &value)?;
+                }
+                if cells.iter().all(|cell| cell.x() == cells[0].x()) {
+                    eliminate(cx, &cells, deps.y_line(), &value)?;
+                }
+            }
+            // ボックス・ラインの削減: 行または列の候補が1つのボックスに揃う。
+            for line in [dependencies.x_line(), dependencies.y_line()] {
+                let cells = candidates(cx, line, &value);
+                if cells.len() > 1 {
+                    let region = |place: &Place<S>| {
+                        let region = cx.dependencies(place).region();
+                        region.into_iter().next()
+                    };
+                    let first = region(&cells[0]);
+                    if cells.iter().all(|cell| region(cell) == first) {
+                        let target = cx.dependencies(&cells[0]).region();
+                        eliminate(cx, &cells, target, &value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn is_standard(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,160 @@
+pub mod arrow;
+pub mod chess;
+pub mod edge;
+pub mod inequality;
+pub mod kenken;
+pub mod killer;
+pub mod line;
+pub mod locked;
+pub mod parity;
+pub mod sandwich;
+pub mod skyscraper;
+pub mod subsets;
+pub mod sudoku;
+pub mod thermo;
+
+use super::RuleViolationError;
+use super::entropy::*;
+use super::layout::*;
+use super::place::*;
+use super::size::*;
+use std::collections::VecDeque;
+
+pub use arrow::Arrow;
+pub use chess::{AntiKing, AntiKnight};
+pub use edge::{Edge, Mark, NegativeEdges};
+pub use inequality::Inequality;
+pub use kenken::{KenKen, Operation};
+pub use killer::{Cage, LittleKiller};
+pub use line::{Palindrome, Renban, Whisper};
+pub use locked::LockedCandidates;
+pub use parity::{Parity, ParityCells};
+pub use sandwich::{Line, Sandwich};
+pub use skyscraper::{Side, Skyscraper};
+pub use subsets::{HiddenSubsets, NakedSubsets};
+pub use sudoku::SudokuRule;
+pub use thermo::Thermometer;
+
+/// EntropyFieldの伝播に参加するルールを表すトレイトです。
+/// セルのエントロピーが縮小する度に通知を受け、
+/// 他のセルの可能性を否定することで伝播を進めます。
+/// 表示にはルール違反が検出された際に、そのルールを識別するための説明を返します。
+pub trait Constraint<S: Size = Standard>: std::fmt::Debug + std::fmt::Display {
+    /// ルールが盤面に追加された時に一度だけ呼ばれます。
+    /// 既定の実装では、それまでに否定された全ての可能性について
+    /// on_shrinkを呼びます。
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        for i in 0..S::CELLS {
+            let place = unsafe { Place::new_from_raw_unchecked(i) };
+            let disabled = cx.entropy_at(&place).disabled_values();
+            if !disabled.is_empty() {
+                self.on_shrink(cx, &place, disabled)?;
+            }
+        }
+        Ok(())
+    }
+    /// 指定された位置のセルのエントロピーが縮小した時に呼ばれます。
+    /// disabledはその縮小で否定された値です。
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        disabled: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>>;
+    /// 数独の基本ルールと同じ解のみを許すルールかどうかを返します。
+    /// 基本ルールから導ける候補の否定のみを行うルールはtrueを返します。
+    /// 既定の実装では、解を絞り込む追加のルールとみなしfalseを返します。
+    fn is_standard(&self) -> bool {
+        false
+    }
+}
+
+/// 伝播中の盤面を表し、Constraintからのセルの操作を受け付けます。
+/// エントロピーを縮小させた操作は記録され、後で全てのConstraintに通知されます。
+pub struct Propagator<'a, S: Size = Standard> {
+    cells: &'a mut [Entropy<S>],
+    layout: &'a Layout<S>,
+    /// エントロピーが縮小したがまだ通知していないセルと、否定された値
+    changes: VecDeque<(Place<S>, ValueIter<S>)>,
+}
+
+impl<'a, S: Size> Propagator<'a, S> {
+    pub(crate) fn new(cells: &'a mut [Entropy<S>], layout: &'a Layout<S>) -> Self {
+        Propagator {
+            cells,
+            layout,
+            changes: VecDeque::new(),
+        }
+    }
+
+    /// 指定された位置のエントロピーを返します。
+    pub fn entropy_at(&self, place: &Place<S>) -> &Entropy<S> {
+        &self.cells[*place.raw()]
+    }
+
+    /// 盤面の構成を返します。
+    pub fn layout(&self) -> &'a Layout<S> {
+        self.layout
+    }
+
+    /// この盤面において、指定された位置に直接的に影響のあるPlaceを返します。
+    pub fn dependencies<'b>(&self, place: &'b Place<S>) -> Dependencies<'b, S>
+    where
+        'a: 'b,
+    {
+        place.dependencies(self.layout)
+    }
+
+    /// 指定された位置のセルにエントロピーを重ねあわせます。
+    /// 可能性を削除した場合はOk(true)を、何も削除しなかった場合はOk(false)を返します。
+    /// 重ねあわせた結果不能となった場合はErr(RuleViolationError)を返します。
+    pub fn superimpose(
+        &mut self,
+        place: &Place<S>,
+        into_entropy: impl Into<Entropy<S>>,
+    ) -> Result<bool, RuleViolationError<S>> {
+        let disabled = self.cells[*place.raw()]
+            .superimpose(into_entropy)
+            .map_err(|err| RuleViolationError::new(err, place.to_owned()))?;
+        if disabled.is_empty() {
+            Ok(false)
+        } else {
+            self.changes.push_back((place.to_owned(), disabled));
+            Ok(true)
+        }
+    }
+
+    /// 指定された位置のセルから値の可能性を否定します。
+    /// 可能性を削除した場合はOk(true)を、既に可能性がなかった場合はOk(false)を返します。
+    /// 否定した結果不能となった場合はErr(RuleViolationError)を返します。
+    pub fn disable(
+        &mut self,
+        place: &Place<S>,
+        value: &Value<S>,
+    ) -> Result<bool, RuleViolationError<S>> {
+        let disabled = self.cells[*place.raw()]
+            .disable(value)
+            .map_err(|err| RuleViolationError::new(err, place.to_owned()))?;
+        if disabled {
+            let entropy: Entropy<S> = value.to_owned().into();
+            self.changes
+                .push_back((place.to_owned(), entropy.into_iter()));
+        }
+        Ok(disabled)
+    }
+
+    /// まだ通知していない変更を1つ取り出します。
+    pub(crate) fn pop_change(&mut self) -> Option<(Place<S>, ValueIter<S>)> {
+        self.changes.pop_front()
+    }
+}
+
+/// セルの列が空でなく、重複していないかどうかを返します。
+/// 複数のセルを組とするルールの生成時の検証に用います。
+fn is_path<S: Size>(cells: &[Place<S>]) -> bool {
+    !cells.is_empty()
+        && cells
+            .iter()
+            .enumerate()
+            .all(|(i, place)| !cells[..i].contains(place))
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    #[test]
+    fn masks() {
+        let table = "o...e\n.o\n\n........e";
+        let parities: ParityCells = table.parse().unwrap();
+        let mut field = EntropyField::new();
+        field.add_constraint(parities).unwrap();
+        let odd = field.entropy_at(&Place::new(0, 0).unwrap());
+        assert_eq!(odd, &Entropy::try_from(0b1010101010).unwrap());
+        let even = field.entropy_at(&Place::new(8, 3).unwrap());
+        assert_eq!(even, &Entropy::try_from(0b0101010100).unwrap());
+        assert_eq!(field.entropy_at(&Place::new(1, 0).unwrap()).len(), 9);
+        let error = field
+            .insert(Place::new(1, 1).unwrap(), Value::TWO)
+            .unwrap_err();
+        assert!(error.rule().is_none());
+
+        field.insert(Place::new(3, 4).unwrap(), Value::TWO).unwrap();
+        let error = field
+            .add_constraint(ParityCells::new(vec![(
+                Place::new(3, 4).unwrap(),
+                Parity::Odd,
+            )]))
+            .unwrap_err();
+        assert_eq!(error.rule().unwrap().to_string(), "偶奇: (3, 4)奇数");
+        assert!("o.x".parse::<ParityCells>().is_err());
+    }
+}
+
+use super::*;
+
+/// セルに入る値の偶奇です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// 奇数のみが入る。
+    Odd,
+    /// 偶数のみが入る。
+    Even,
+}
+
+impl Parity {
+    /// この偶奇の値のみを残すエントロピーを返します。
+    pub fn mask<S: Size>(self) -> Entropy<S> {
+        let pattern: BITS = match self {
+            Parity::Odd => 0xAAAAAAAA,
+            Parity::Even => 0x55555555,
+        };
+        let all: BITS = Entropy::<S>::new().into();
+        (all & pattern).try_into().unwrap()
+    }
+}
+
+impl std::fmt::Display for Parity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Parity::Odd => write!(f, "奇数"),
+            Parity::Even => write!(f, "偶数"),
+        }
+    }
+}
+
+/// 偶奇が指定されたセル(偶奇数独の色付きのセル)による制約です。
+/// 初めに各セルのエントロピーを指定された偶奇の値に絞り、以降の伝播には関与しません。
+#[derive(Debug, Clone)]
+pub struct ParityCells<S: Size = Standard> {
+    cells: Vec<(Place<S>, Parity)>,
+}
+
+impl<S: Size> ParityCells<S> {
+    /// セルとその偶奇の組から新しいParityCellsを返します。
+    pub fn new(cells: Vec<(Place<S>, Parity)>) -> Self {
+        ParityCells { cells }
+    }
+    /// 偶奇が指定されたセルを返します。
+    pub fn cells(&self) -> &[(Place<S>, Parity)] {
+        &self.cells
+    }
+}
+
+impl<S: Size> std::str::FromStr for ParityCells<S> {
+    type Err = ();
+    /// 1行に盤面の一行を並べた表から偶奇を読み込みます。
+    /// `o`のセルは奇数、`e`のセルは偶数となり、`.`のセルは指定しません。
+    /// 行の末尾や末尾の行は省略できます。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let parity = match c {
+                    'o' => Parity::Odd,
+                    'e' => Parity::Even,
+                    '.' => continue,
+                    _ => return Err(()),
+                };
+                cells.push((Place::new(x, y).ok_or(())?, parity));
+            }
+        }
+        Ok(ParityCells::new(cells))
+    }
+}
+
+impl<S: Size> std::fmt::Display for ParityCells<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "偶奇:")?;
+        for (place, parity) in &self.cells {
+            write!(f, " {place}{parity}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> Constraint<S> for ParityCells<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        for (place, parity) in &self.cells {
+            cx.superimpose(place, parity.mask())?;
+        }
+        Ok(())
+    }
+    fn on_shrink(
+        &self,
+        _: &mut Propagator<'_, S>,
+        _: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    #[test]
+    fn bounds() {
+        let mut field = EntropyField::new();
+        // 2から8までの合計は35なので、1と9は両端に入る。
+        field.add_constraint(Sandwich::row(0, 35).unwrap()).unwrap();
+        let ends = Entropy::try_from(0b1000000010).unwrap();
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()), &ends);
+        assert_eq!(field.entropy_at(&Place::new(8, 0).unwrap()), &ends);
+        assert_eq!(field.entropy_at(&Place::new(4, 0).unwrap()).len(), 7);
+        // 合計が0ならば1と9は隣りあう。
+        field
+            .add_constraint(Sandwich::column(4, 0).unwrap())
+            .unwrap();
+        field.insert(Place::new(4, 5).unwrap(), Value::ONE).unwrap();
+        assert!(
+            field
+                .entropy_at(&Place::new(4, 4).unwrap())
+                .is_possible(&Value::NINE)
+        );
+        assert!(
+            !field
+                .entropy_at(&Place::new(4, 3).unwrap())
+                .is_possible(&Value::NINE)
+        );
+
+        let mut field = EntropyField::new();
+        field.insert(Place::new(4, 4).unwrap(), Value::ONE).unwrap();
+        let error = field
+            .add_constraint(Sandwich::column(4, 35).unwrap())
+            .unwrap_err();
+        assert_eq!(error.rule().unwrap().to_string(), "サンドイッチ(4列目): 35");
+        assert!(Sandwich::<Standard>::row(9, 0).is_none());
+    }
+}
+
+use super::killer::supported_values;
+use super::*;
+
+/// サンドイッチの手がかりが与えられる行または列です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    /// 指定されたY座標の横一行
+    Row(usize),
+    /// 指定されたX座標の縦一列
+    Column(usize),
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Line::Row(y) => write!(f, "{y}行目"),
+            Line::Column(x) => write!(f, "{x}列目"),
+        }
+    }
+}
+
+/// サンドイッチ数独の手がかりです。
+/// 行または列で、最小の値と最大の値に挟まれたセルの値の合計が指定された値となります。
+/// 最小の値と最大の値が入り得る位置の組ごとに、挟まれたセルの組み合わせを調べ、
+/// いずれかの組で成り立つ値のみを各セルに残します。
+#[derive(Debug, Clone)]
+pub struct Sandwich<S: Size = Standard> {
+    line: Line,
+    /// 行または列のセル
+    cells: Vec<Place<S>>,
+    sum: BITS,
+}
+
+impl<S: Size> Sandwich<S> {
+    /// 指定されたY座標の行についての手がかりを返します。
+    /// Y座標が範囲外である場合はNoneが返ります。
+    pub fn row(y: usize, sum: BITS) -> Option<Self> {
+        let cells = Place::new(0, y)?.dependencies(&Layout::new()).x_line();
+        Some(Sandwich {
+            line: Line::Row(y),
+            cells: cells.into_iter().collect(),
+            sum,
+        })
+    }
+    /// 指定されたX座標の列についての手がかりを返します。
+    /// X座標が範囲外である場合はNoneが返ります。
+    pub fn column(x: usize, sum: BITS) -> Option<Self> {
+        let cells = Place::new(x, 0)?.dependencies(&Layout::new()).y_line();
+        Some(Sandwich {
+            line: Line::Column(x),
+            cells: cells.into_iter().collect(),
+            sum,
+        })
+    }
+    /// 手がかりが与えられた行または列を返します。
+    pub fn line(&self) -> Line {
+        self.line
+    }
+    /// 挟まれたセルの値の合計を返します。
+    pub fn sum(&self) -> BITS {
+        self.sum
+    }
+}
+
+impl<S: Size> std::fmt::Display for Sandwich<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "サンドイッチ({}): {}", self.line, self.sum)
+    }
+}
+
+impl<S: Size> Constraint<S> for Sandwich<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let (min, max): (BITS, BITS) = (1 << 1, 1 << S::SIDE);
+        let candidates: Vec<BITS> = self
+            .cells
+            .iter()
+            .map(|place| cx.entropy_at(place).to_owned().into())
+            .collect();
+        let mut supported = vec![0; candidates.len()];
+        for i in 0..candidates.len() {
+            for j in 0..candidates.len() {
+                if i == j || candidates[i] & min == 0 || candidates[j] & max == 0 {
+                    continue;
+                }
+                let (start, end) = (i.min(j), i.max(j));
+                let inner: Vec<BITS> = candidates[start + 1..end]
+                    .iter()
+                    .map(|bits| bits & !(min | max))
+                    .collect();
+                let inner = supported_values(&inner, self.sum);
+                if inner.is_empty() && self.sum != 0 || inner.contains(&0) {
+                    continue;
+                }
+                // この位置の組で手がかりが成り立つ。
+                supported[i] |= min;
+                supported[j] |= max;
+                for (k, bits) in inner.into_iter().enumerate() {
+                    supported[start + 1 + k] |= bits;
+                }
+                for k in (0..start).chain(end + 1..candidates.len()) {
+                    supported[k] |= candidates[k] & !(min | max);
+                }
+            }
+        }
+        for (place, supported) in self.cells.iter().zip(supported) {
+            let entropy: Entropy<S> = supported.try_into().unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
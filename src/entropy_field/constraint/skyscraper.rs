@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::solve_first;
+    type Size = Latin<5>;
+    #[test]
+    fn visibility() {
+        let mut field = EntropyField::<Size>::new();
+        // 5つのビルが全て見えるのは、低い順に並んでいる場合のみとなる。
+        field
+            .add_constraint(Skyscraper::new(Side::Left, 0, 5).unwrap())
+            .unwrap();
+        for x in 0..5 {
+            let value = Value::new(x as BITS + 1).unwrap();
+            assert_eq!(field.entropy_at(&Place::new(x, 0).unwrap()), &value.into());
+        }
+        // 1つしか見えない場合は、手前のビルが最も高い。
+        field
+            .add_constraint(Skyscraper::new(Side::Bottom, 2, 1).unwrap())
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(2, 4).unwrap()),
+            &Value::new(5).unwrap().into()
+        );
+        // 1の奥で最初に見えるビルが、最も高いビルとなる。
+        field
+            .add_constraint(Skyscraper::new(Side::Top, 0, 2).unwrap())
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(0, 1).unwrap()),
+            &Value::new(5).unwrap().into()
+        );
+        let error = field
+            .add_constraint(Skyscraper::new(Side::Right, 0, 2).unwrap())
+            .unwrap_err();
+        assert_eq!(error.rule().unwrap().to_string(), "ビルディング(右0): 2");
+        assert!(Skyscraper::<Size>::new(Side::Top, 5, 1).is_none());
+    }
+    #[test]
+    fn solve() {
+        let mut field = EntropyField::<Latin<4>>::new();
+        let clues = [
+            (Side::Top, 1, 2),
+            (Side::Bottom, 1, 2),
+            (Side::Left, 1, 2),
+            (Side::Bottom, 2, 3),
+            (Side::Bottom, 3, 3),
+        ];
+        for (side, index, visible) in clues {
+            field
+                .add_constraint(Skyscraper::new(side, index, visible).unwrap())
+                .unwrap();
+        }
+        // 手がかりによる枝刈りを受けながら、解が見つかる。
+        let found = solve_first(field);
+        let rows = [[1, 2, 3, 4], [2, 1, 4, 3], [3, 4, 2, 1], [4, 3, 1, 2]];
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, value) in row.into_iter().enumerate() {
+                assert_eq!(
+                    found.entropy_at(&Place::new(x, y).unwrap()),
+                    &Value::new(value).unwrap().into()
+                );
+            }
+        }
+    }
+}
+
+use super::*;
+use std::collections::HashSet;
+
+/// 手がかりが置かれる盤面の外側の辺です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// 上から列を見下ろす。
+    Top,
+    /// 下から列を見上げる。
+    Bottom,
+    /// 左から行を見る。
+    Left,
+    /// 右から行を見る。
+    Right,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Top => write!(f, "上"),
+            Side::Bottom => write!(f, "下"),
+            Side::Left => write!(f, "左"),
+            Side::Right => write!(f, "右"),
+        }
+    }
+}
+
+/// ビルディング(スカイスクレイパー)の手がかりです。
+/// 値をビルの高さとみなし、盤面の外から行または列を見たときに、
+/// 手前のビルに隠れずに見えるビルの数が指定された値となります。
+/// 手前から順に、使った値と最も高いビル、見えたビルの数の状態を辿り、
+/// 手がかりを満たす並びに現れる値のみを残します。
+#[derive(Debug, Clone)]
+pub struct Skyscraper<S: Size = Standard> {
+    side: Side,
+    index: usize,
+    /// 手前から順に並べたセル
+    cells: Vec<Place<S>>,
+    visible: BITS,
+}
+
+impl<S: Size> Skyscraper<S> {
+    /// 手がかりが置かれる辺と、その辺に沿った位置、見えるビルの数から新しいSkyscraperを返します。
+    /// 位置が範囲外である場合はNoneが返ります。
+    pub fn new(side: Side, index: usize, visible: BITS) -> Option<Self> {
+        let mut cells: Vec<Place<S>> = match side {
+            Side::Top | Side::Bottom => Place::new(index, 0)?.dependencies(&Layout::new()).y_line(),
+            Side::Left | Side::Right => Place::new(0, index)?.dependencies(&Layout::new()).x_line(),
+        }
+        .into_iter()
+        .collect();
+        if matches!(side, Side::Bottom | Side::Right) {
+            cells.reverse();
+        }
+        Some(Skyscraper {
+            side,
+            index,
+            cells,
+            visible,
+        })
+    }
+    /// 手がかりが置かれる辺を返します。
+    pub fn side(&self) -> Side {
+        self.side
+    }
+    /// 手前から順に並べたセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+    /// 見えるビルの数を返します。
+    pub fn visible(&self) -> BITS {
+        self.visible
+    }
+}
+
+impl<S: Size> std::fmt::Display for Skyscraper<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ビルディング({}{}): {}",
+            self.side, self.index, self.visible
+        )
+    }
+}
+
+/// 手前から値を並べた途中の状態です。
+/// 使った値の集合、最も高いビルの高さ、見えたビルの数の組となります。
+type State = (BITS, BITS, BITS);
+
+impl<S: Size> Constraint<S> for Skyscraper<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let candidates: Vec<BITS> = self
+            .cells
+            .iter()
+            .map(|place| cx.entropy_at(place).to_owned().into())
+            .collect();
+        let next = |(used, highest, seen): State, value: BITS| -> Option<State> {
+            if used & (1 << value) != 0 {
+                return None;
+            }
+            let state = if value > highest {
+                (used | 1 << value, value, seen + 1)
+            } else {
+                (used | 1 << value, highest, seen)
+            };
+            (state.2 <= self.visible).then_some(state)
+        };
+        // layers[i]は手前からi個のセルに値を並べた状態
+        let mut layers: Vec<HashSet<State>> = vec![HashSet::from([(0, 0, 0)])];
+        for &candidate in &candidates {
+            let mut states = HashSet::new();
+            for &state in layers.last().unwrap() {
+                for value in Entropy::<S>::try_from(candidate).unwrap() {
+                    states.extend(next(state, value.into()));
+                }
+            }
+            layers.push(states);
+        }
+        // 奥から順に、手がかりを満たす並びに繋がる状態だけを残していく。
+        let mut alive: HashSet<State> = layers
+            .pop()
+            .unwrap()
+            .into_iter()
+            .filter(|&(_, _, seen)| seen == self.visible)
+            .collect();
+        let mut supported = vec![0; candidates.len()];
+        for (i, layer) in layers.into_iter().enumerate().rev() {
+            let mut previous = HashSet::new();
+            for state in layer {
+                for value in Entropy::<S>::try_from(candidates[i]).unwrap() {
+                    let value = BITS::from(value);
+                    if next(state, value).is_some_and(|state| alive.contains(&state)) {
+                        supported[i] |= 1 << value;
+                        previous.insert(state);
+                    }
+                }
+            }
+            alive = previous;
+        }
+        for (place, supported) in self.cells.iter().zip(supported) {
+            let entropy: Entropy<S> = supported.try_into().unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
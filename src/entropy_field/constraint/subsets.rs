@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    fn entropy(values: &[BITS]) -> Entropy {
+        let bits = values.iter().fold(0, |bits, value| bits | 1 << value);
+        Entropy::try_from(bits).unwrap()
+    }
+    fn without(values: &[BITS]) -> Entropy {
+        let bits = values
+            .iter()
+            .fold(0b1111111110, |bits, value| bits & !(1 << value));
+        Entropy::try_from(bits).unwrap()
+    }
+    #[test]
+    fn naked() {
+        let mut field = EntropyField::new();
+        field.add_constraint(NakedSubsets::new(4).unwrap()).unwrap();
+        // (0, 0)と(1, 0)が1か2ならば、同じ行とボックスの他のセルに1と2は入らない。
+        field
+            .insert(Place::new(0, 0).unwrap(), entropy(&[1, 2]))
+            .unwrap();
+        field
+            .insert(Place::new(1, 0).unwrap(), entropy(&[1, 2]))
+            .unwrap();
+        for (x, y) in [(5, 0), (2, 2)] {
+            let entropy = field.entropy_at(&Place::new(x, y).unwrap());
+            assert!(!entropy.is_possible(&Value::ONE));
+            assert!(!entropy.is_possible(&Value::TWO));
+        }
+        assert_eq!(field.entropy_at(&Place::new(0, 5).unwrap()).len(), 9);
+        // 3つのセルに3から5の値が揃うと、同じ行の他のセルに3から5は入らない。
+        for (x, values) in [(0, &[3, 4, 5][..]), (4, &[3, 4]), (8, &[4, 5])] {
+            field
+                .insert(Place::new(x, 4).unwrap(), entropy(values))
+                .unwrap();
+        }
+        assert_eq!(
+            field.entropy_at(&Place::new(2, 4).unwrap()),
+            &entropy(&[1, 2, 6, 7, 8, 9])
+        );
+        assert!(NakedSubsets::new(1).is_none());
+    }
+    #[test]
+    fn hidden() {
+        let mut field = EntropyField::new();
+        field
+            .add_constraint(HiddenSubsets::new(3).unwrap())
+            .unwrap();
+        // 1行目で1と2の入る可能性が(0, 0)と(1, 0)のみとなると、その2つは1か2となる。
+        let rest = entropy(&[3, 4, 5, 6, 7, 8, 9]);
+        for x in 2..9 {
+            field
+                .insert(Place::new(x, 0).unwrap(), rest.clone())
+                .unwrap();
+        }
+        for x in [0, 1] {
+            assert_eq!(
+                field.entropy_at(&Place::new(x, 0).unwrap()),
+                &entropy(&[1, 2])
+            );
+        }
+        // 3つの値が2つのセルにしか入らない場合は矛盾する。
+        let mut field: EntropyField = EntropyField::new();
+        let rest = entropy(&[4, 5, 6, 7, 8, 9]);
+        for y in 2..9 {
+            field
+                .insert(Place::new(4, y).unwrap(), rest.clone())
+                .unwrap();
+        }
+        let error = field
+            .add_constraint(HiddenSubsets::new(3).unwrap())
+            .unwrap_err();
+        assert_eq!(
+            error.rule().unwrap().to_string(),
+            "ヒドゥンサブセット(3値まで)"
+        );
+        // 収束したセルの値が否定の伝播を待つ間に、その値を偽のペアとして扱わない。
+        let mut field = EntropyField::new();
+        field
+            .add_constraint(HiddenSubsets::new(3).unwrap())
+            .unwrap();
+        for (x, y) in (0..3).flat_map(|y| (0..3).map(move |x| (x, y))).skip(2) {
+            field
+                .insert(Place::new(x, y).unwrap(), without(&[1]))
+                .unwrap();
+        }
+        for x in [2, 3, 4, 7, 8] {
+            field
+                .insert(Place::new(x, 0).unwrap(), without(&[1, 2]))
+                .unwrap();
+        }
+        field
+            .insert(Place::new(1, 0).unwrap(), without(&[2]))
+            .unwrap();
+        field
+            .insert(Place::new(0, 0).unwrap(), without(&[1, 2]))
+            .unwrap();
+        assert_eq!(field.entropy_at(&Place::new(1, 0).unwrap()), &entropy(&[1]));
+    }
+}
+
+use super::*;
+
+/// ブロック内の組み合わせを調べる際の、セルの添字の組と値の集合です。
+type Subset = (Vec<usize>, BITS);
+
+/// items[start..]から、集合の和の値の個数がmax以下となる2個以上の組み合わせを全て列挙します。
+fn subsets(items: &[BITS], start: usize, max: usize, chosen: Subset, found: &mut Vec<Subset>) {
+    for i in start..items.len() {
+        let union = chosen.1 | items[i];
+        if union.count_ones() as usize > max {
+            continue;
+        }
+        let mut indices = chosen.0.clone();
+        indices.push(i);
+        let subset = (indices, union);
+        if subset.0.len() >= 2 {
+            found.push(subset.clone());
+        }
+        subsets(items, i + 1, max, subset, found);
+    }
+}
+
+/// 指定されたセルに関係するブロックの、収束していないセルを返します。
+fn unsolved_blocks<S: Size>(cx: &Propagator<'_, S>, place: &Place<S>) -> Vec<Vec<Place<S>>> {
+    cx.dependencies(place)
+        .into_iter()
+        .map(|block| {
+            block
+                .into_iter()
+                .filter(|place| cx.entropy_at(place).len() > 1)
+                .collect()
+        })
+        .collect()
+}
+
+/// ネイキッドサブセット(ネイキッドペア、トリプル、クアッド)の検出です。
+/// ブロック内のn個のセルに入り得る値を合わせてn種類しかない場合、
+/// それらの値はそのn個のセルに入るので、ブロック内の他のセルから否定します。
+/// 伝播の強さを選べるよう、調べるセルの個数の上限を指定して盤面に追加します。
+#[derive(Debug, Clone)]
+pub struct NakedSubsets {
+    max: usize,
+}
+
+impl NakedSubsets {
+    /// 調べるセルの個数の上限から新しいNakedSubsetsを返します。
+    /// 上限が2未満の場合はNoneが返ります。
+    pub fn new(max: usize) -> Option<Self> {
+        (max >= 2).then_some(NakedSubsets { max })
+    }
+    /// 調べるセルの個数の上限を返します。
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl std::fmt::Display for NakedSubsets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ネイキッドサブセット({}セルまで)", self.max)
+    }
+}
+
+impl<S: Size> Constraint<S> for NakedSubsets {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        for unsolved in unsolved_blocks(cx, place) {
+            let items: Vec<BITS> = unsolved
+                .iter()
+                .map(|place| cx.entropy_at(place).to_owned().into())
+                .collect();
+            let mut found = Vec::new();
+            subsets(&items, 0, self.max, (Vec::new(), 0), &mut found);
+            for (indices, union) in found {
+                let values = union.count_ones() as usize;
+                if values < indices.len() {
+                    // 値の種類がセルの個数より少なく、いずれかのセルに値が入らない。
+                    let conflict: Entropy<S> = Entropy::try_from(0 as BITS).unwrap();
+                    cx.superimpose(&unsolved[indices[0]], conflict)?;
+                } else if values == indices.len() {
+                    let rest = BITS::from(Entropy::<S>::new()) & !union;
+                    for (i, other) in unsolved.iter().enumerate() {
+                        if !indices.contains(&i) {
+                            cx.superimpose(other, Entropy::try_from(rest).unwrap())?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn is_standard(&self) -> bool {
+        true
+    }
+}
+
+/// ヒドゥンサブセット(ヒドゥンペア、トリプル、クアッド)の検出です。
+/// ブロック内でn種類の値の入り得るセルを合わせてn個しかない場合、
+/// そのn個のセルにはそれらの値のみが入るので、セルのエントロピーをそれらの値に絞ります。
+/// 伝播の強さを選べるよう、調べる値の種類の上限を指定して盤面に追加します。
+#[derive(Debug, Clone)]
+pub struct HiddenSubsets {
+    max: usize,
+}
+
+impl HiddenSubsets {
+    /// 調べる値の種類の上限から新しいHiddenSubsetsを返します。
+    /// 上限が2未満の場合はNoneが返ります。
+    pub fn new(max: usize) -> Option<Self> {
+        (max >= 2).then_some(HiddenSubsets { max })
+    }
+    /// 調べる値の種類の上限を返します。
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl std::fmt::Display for HiddenSubsets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ヒドゥンサブセット({}値まで)", self.max)
+    }
+}
+
+impl<S: Size> Constraint<S> for HiddenSubsets {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        for block in cx.dependencies(place) {
+            let (solved, unsolved): (Vec<Place<S>>, Vec<Place<S>>) = block
+                .into_iter()
+                .partition(|place| cx.entropy_at(place).len() == 1);
+            // 既に収束したセルに入った値は、否定の伝播を待つ間も他のセルに残り得るため除く。
+            let placed = solved.iter().fold(0, |placed, place| {
+                placed | BITS::from(cx.entropy_at(place).to_owned())
+            });
+            // 値ごとに、入り得るセルの添字の集合を求める。
+            let (values, items): (Vec<BITS>, Vec<BITS>) = Entropy::<S>::new()
+                .into_iter()
+                .filter(|value| placed & 1 << BITS::from(value.clone()) == 0)
+                .map(|value| {
+                    let cells = unsolved
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, place)| cx.entropy_at(place).is_possible(&value))
+                        .fold(0, |cells, (i, _)| cells | 1 << i);
+                    (1 << BITS::from(value), cells)
+                })
+                .filter(|&(_, cells)| cells != 0)
+                .unzip();
+            let mut found = Vec::new();
+            subsets(&items, 0, self.max, (Vec::new(), 0), &mut found);
+            for (indices, cells) in found {
+                let count = cells.count_ones() as usize;
+                if count < indices.len() {
+                    // セルの個数が値の種類より少なく、いずれかの値が入らない。
+                    let conflict: Entropy<S> = Entropy::try_from(0 as BITS).unwrap();
+                    let i = cells.trailing_zeros() as usize;
+                    cx.superimpose(&unsolved[i], conflict)?;
+                } else if count == indices.len() {
+                    let mask = indices.iter().fold(0, |mask, &i| mask | values[i]);
+                    let entropy: Entropy<S> = Entropy::try_from(mask).unwrap();
+                    for (i, place) in unsolved.iter().enumerate() {
+                        if cells & 1 << i != 0 {
+                            cx.superimpose(place, entropy.clone())?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn is_standard(&self) -> bool {
+        true
+    }
+}
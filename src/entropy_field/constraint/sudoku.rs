@@ -0,0 +1,83 @@
+use super::*;
+
+/// 数独の基本ルールです。
+/// 関係するブロック内のセルに同じ値が入らないよう伝播します。
+/// 値が収束したセルからはその値を他のセルから否定し(naked single)、
+/// ブロック内で値の入る可能性が1つのセルに限られた場合はそのセルを収束させます(hidden single)。
+#[derive(Debug, Clone, Default)]
+pub struct SudokuRule;
+
+impl std::fmt::Display for SudokuRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "数独のルール")
+    }
+}
+
+impl<S: Size> Constraint<S> for SudokuRule {
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        disabled: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        for disabled_value in disabled {
+            // 削除された可能性について探索
+            search_uniqueness_around(cx, &disabled_value, place)?;
+        }
+        // 指定されたセルのエントロピーが収束した場合。
+        // (値が一つのとき)
+        if let Ok(value) = cx.entropy_at(place).to_owned().try_into() {
+            let value: Value<S> = value;
+            for related_block in cx.dependencies(place) {
+                for related_place in related_block {
+                    if &related_place != place {
+                        cx.disable(&related_place, &value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn is_standard(&self) -> bool {
+        true
+    }
+}
+
+/// セルの値を1つ否定する度に呼ぶ。
+/// そのセルに関係するセルが唯一の値になる可能性があるので、
+/// その値について唯一の可能性の位置となったセルを収束させる。
+fn search_uniqueness_around<S: Size>(
+    cx: &mut Propagator<'_, S>,
+    disabled_value: &Value<S>,
+    changing_place: &Place<S>,
+) -> Result<(), RuleViolationError<S>> {
+    for block in cx.dependencies(changing_place) {
+        let mut first: Option<Place<S>> = None;
+        let mut unique = true;
+        for affected_place in block {
+            // 与えられた一列(y_line)、一行(x_line)、一区画(region)
+            // (:block)のうちで、与えられた値が唯一のものを探す。
+            if &affected_place != changing_place
+                && cx.entropy_at(&affected_place).is_possible(disabled_value)
+            {
+                match first {
+                    Some(_) => {
+                        // まだ複数のセルで可能性がある。
+                        unique = false;
+                        break;
+                    }
+                    None => {
+                        // 可能性のある最初のセル
+                        first = Some(affected_place);
+                    }
+                }
+            }
+        }
+        if unique {
+            // 可能性のあるセルが1つもない場合は、否定されたセルに値を戻そうとして競合する。
+            let unique_place = first.unwrap_or_else(|| changing_place.to_owned());
+            cx.superimpose(&unique_place, disabled_value.to_owned())?;
+        }
+    }
+    Ok(())
+}
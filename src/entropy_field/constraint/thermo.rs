@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entropy_field::EntropyField;
+    use crate::testing::places;
+    fn thermometer(cells: &[(usize, usize)]) -> Thermometer {
+        Thermometer::new(places(cells)).unwrap()
+    }
+    #[test]
+    fn bounds() {
+        let mut field = EntropyField::new();
+        field
+            .add_constraint(thermometer(&[(0, 0), (1, 0), (2, 0)]))
+            .unwrap();
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()).len(), 7);
+        assert_eq!(field.entropy_at(&Place::new(1, 0).unwrap()).len(), 7);
+        assert!(
+            !field
+                .entropy_at(&Place::new(2, 0).unwrap())
+                .is_possible(&Value::TWO)
+        );
+        field
+            .insert(Place::new(1, 0).unwrap(), Value::EIGHT)
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(2, 0).unwrap()),
+            &Value::NINE.into()
+        );
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()).len(), 7);
+
+        // 長さ9のサーモメーターは1から9に確定する。
+        let mut field = EntropyField::new();
+        let cells: Vec<_> = (0..9).map(|y| (4, y)).collect();
+        field.add_constraint(thermometer(&cells)).unwrap();
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            let value = Value::new(i as BITS + 1).unwrap();
+            assert_eq!(field.entropy_at(&Place::new(x, y).unwrap()), &value.into());
+        }
+    }
+    #[test]
+    fn violation() {
+        let mut field = EntropyField::new();
+        field.insert(Place::new(2, 2).unwrap(), Value::ONE).unwrap();
+        field
+            .add_constraint(thermometer(&[(0, 0), (1, 1)]))
+            .unwrap();
+        // (2, 2)が1なので、それより小さい値は入らない。
+        let error = field
+            .add_constraint(thermometer(&[(3, 3), (2, 2)]))
+            .unwrap_err();
+        assert_eq!(
+            error.rule().unwrap().to_string(),
+            "サーモメーター: (3, 3) -> (2, 2)"
+        );
+    }
+}
+
+use super::*;
+
+/// サーモメーターです。
+/// 球部のセルから順に、値が狭義単調増加となります。
+/// 球部からの位置と前後のセルの現在の可能性から、各セルの値の上限と下限を絞り込みます。
+#[derive(Debug, Clone)]
+pub struct Thermometer<S: Size = Standard> {
+    /// 球部から順に並べたセル
+    cells: Vec<Place<S>>,
+}
+
+impl<S: Size> Thermometer<S> {
+    /// 球部から順に並べたセルから新しいThermometerを返します。
+    /// セルが空である、または重複している場合はNoneが返ります。
+    pub fn new(cells: Vec<Place<S>>) -> Option<Self> {
+        is_path(&cells).then_some(Thermometer { cells })
+    }
+    /// 球部から順に並べたセルを返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+}
+
+impl<S: Size> std::fmt::Display for Thermometer<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "サーモメーター: {}", self.cells[0])?;
+        for place in &self.cells[1..] {
+            write!(f, " -> {place}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> Constraint<S> for Thermometer<S> {
+    fn init(&self, cx: &mut Propagator<'_, S>) -> Result<(), RuleViolationError<S>> {
+        self.on_shrink(cx, &self.cells[0], Entropy::new().disabled_values())
+    }
+    fn on_shrink(
+        &self,
+        cx: &mut Propagator<'_, S>,
+        place: &Place<S>,
+        _: ValueIter<S>,
+    ) -> Result<(), RuleViolationError<S>> {
+        if !self.cells.contains(place) {
+            return Ok(());
+        }
+        let mut bits: Vec<BITS> = self
+            .cells
+            .iter()
+            .map(|place| cx.entropy_at(place).to_owned().into())
+            .collect();
+        // 球部から順に、手前のセルの最小値より大きい値に絞る。
+        for i in 1..bits.len() {
+            let min = bits[i - 1].trailing_zeros();
+            bits[i] &= BITS::MAX.checked_shl(min + 1).unwrap_or(0);
+        }
+        // 先端から順に、奥のセルの最大値より小さい値に絞る。
+        for i in (0..bits.len() - 1).rev() {
+            let max = BITS::BITS - bits[i + 1].leading_zeros();
+            bits[i] &= (1 << max.saturating_sub(1)) - 1;
+        }
+        for (place, bits) in self.cells.iter().zip(bits) {
+            let entropy: Entropy<S> = bits.try_into().unwrap();
+            cx.superimpose(place, entropy)?;
+        }
+        Ok(())
+    }
+}
@@ -32,7 +32,7 @@ mod test {
     #[test]
     fn new_try_converge() {
         for i in 1..=9 {
-            let test_value = Value::new(i).unwrap();
+            let test_value: Value = Value::new(i).unwrap();
             let mut entropy = Entropy::new();
             let rest = entropy.superimpose(test_value.clone()).unwrap();
             assert_eq!(entropy.len(), 1);
@@ -47,17 +47,34 @@ mod test {
         entropy.superimpose(Value::TWO).unwrap();
         assert_eq!(entropy.len(), 1);
     }
+    #[test]
+    fn sized() {
+        assert_eq!(Entropy::<Size4>::new().len(), 4);
+        assert_eq!(Entropy::<Size16>::new().len(), 16);
+        assert_eq!(Entropy::<Size25>::new().len(), 25);
+        assert!(Value::<Size4>::new(4).is_some());
+        assert!(Value::<Size4>::new(5).is_none());
+        assert!(Value::<Size25>::new(25).is_some());
+        Entropy::<Size4>::try_from(Into::<BITS>::into(Entropy::<Size9>::new())).unwrap_err();
+    }
 }
 
+use super::size::*;
+use std::marker::PhantomData;
+
 /// ValueやEntropyが内部的に用いている型です。
 /// コンストラクト時にはこの型で入力を行います。
 pub type BITS = u32;
 
-const MASK: BITS = 0b1111111110;
+/// その盤面の大きさで取り得る値全てのビットを立てたマスクを返します。
+const fn mask<S: Size>() -> BITS {
+    assert!(S::SIDE < BITS::BITS as usize, "盤面が大きすぎます。");
+    ((1 << (S::SIDE + 1)) - 1) & !1
+}
 
 /// 数独の各セルに入っている値の型です。
 #[derive(PartialEq, Eq, Clone, Debug)]
-pub struct Value(BITS);
+pub struct Value<S: Size = Standard>(BITS, PhantomData<S>);
 impl Value {
     pub const ONE: Value = unsafe { Value::new_unchecked(1) };
     pub const TWO: Value = unsafe { Value::new_unchecked(2) };
@@ -68,57 +85,65 @@ impl Value {
     pub const SEVEN: Value = unsafe { Value::new_unchecked(7) };
     pub const EIGHT: Value = unsafe { Value::new_unchecked(8) };
     pub const NINE: Value = unsafe { Value::new_unchecked(9) };
+}
+impl<S: Size> Value<S> {
+    /// 新しいValueを返します。
+    /// 引数の値が1以上、盤面の一辺のセル数以下でない場合はNoneが返ります。
     pub const fn new(value: BITS) -> Option<Self> {
-        if value > 0 && value <= 9 {
+        if value > 0 && value as usize <= S::SIDE {
             Some(unsafe { Self::new_unchecked(value) })
         } else {
             None
         }
     }
+    /// 新しいValueを返します。
+    ///
+    /// # Safety
+    /// 引数の値が1以上、盤面の一辺のセル数以下である必要があります。
     pub const unsafe fn new_unchecked(value: BITS) -> Self {
-        Value(1 << value)
+        Value(1 << value, PhantomData)
     }
 }
 
-impl From<Value> for BITS {
-    fn from(val: Value) -> Self {
+impl<S: Size> From<Value<S>> for BITS {
+    fn from(val: Value<S>) -> Self {
         val.0.trailing_zeros()
     }
 }
 
-impl From<Value> for Entropy {
-    fn from(val: Value) -> Self {
-        Entropy(val.0)
+impl<S: Size> From<Value<S>> for Entropy<S> {
+    fn from(val: Value<S>) -> Self {
+        Entropy(val.0, PhantomData)
     }
 }
 
-impl std::fmt::Display for Value {
+impl<S: Size> std::fmt::Display for Value<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0.trailing_zeros())
+        self.0.trailing_zeros().fmt(f)
     }
 }
 
 pub const BITS_LENGTH: usize = std::mem::size_of::<BITS>();
 
-impl From<Value> for [u8; BITS_LENGTH] {
-    fn from(val: Value) -> Self {
+impl<S: Size> From<Value<S>> for [u8; BITS_LENGTH] {
+    fn from(val: Value<S>) -> Self {
         val.0.to_ne_bytes()
     }
 }
 
-impl TryFrom<[u8; BITS_LENGTH]> for Value {
+impl<S: Size> TryFrom<[u8; BITS_LENGTH]> for Value<S> {
     type Error = ();
     fn try_from(value: [u8; BITS_LENGTH]) -> Result<Self, Self::Error> {
-        let entropy: Entropy = value.try_into()?;
+        let entropy: Entropy<S> = value.try_into()?;
         entropy.try_into()
     }
 }
 
 /// Valueの重複のないイテレータです。
 #[derive(Debug, Clone)]
-pub struct ValueIter(BITS);
+pub struct ValueIter<S: Size = Standard>(BITS, PhantomData<S>);
 
-impl ValueIter {
+impl<S: Size> ValueIter<S> {
     pub fn len(&self) -> BITS {
         self.0.count_ones()
     }
@@ -127,8 +152,8 @@ impl ValueIter {
     }
 }
 
-impl Iterator for ValueIter {
-    type Item = Value;
+impl<S: Size> Iterator for ValueIter<S> {
+    type Item = Value<S>;
     fn next(&mut self) -> Option<Self::Item> {
         match Value::new(self.0.trailing_zeros()) {
             Some(v) => {
@@ -144,11 +169,13 @@ impl Iterator for ValueIter {
 // 初期状態はMASK、
 // 否定された可能性がnの場合、(n+1)桁目が0となる。
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Entropy(BITS);
-impl Entropy {
+pub struct Entropy<S: Size = Standard>(BITS, PhantomData<S>);
+impl<S: Size> Entropy<S> {
+    const MASK: BITS = mask::<S>();
+
     /// 全く収束していない新しいエントロピーを返します。
     pub const fn new() -> Self {
-        Entropy(MASK)
+        Entropy(Self::MASK, PhantomData)
     }
 
     /// エントロピーの大きさを返します。
@@ -161,8 +188,13 @@ impl Entropy {
         self.0 == 0
     }
 
+    /// 既に否定された値のイテレータを返します。
+    pub fn disabled_values(&self) -> ValueIter<S> {
+        ValueIter(!self.0 & Self::MASK, PhantomData)
+    }
+
     /// その値になる可能性があるかどうかを返します。
-    pub fn is_possible(&self, value: &Value) -> bool {
+    pub fn is_possible(&self, value: &Value<S>) -> bool {
         self.0 & value.0 != 0
     }
 
@@ -170,7 +202,7 @@ impl Entropy {
     /// 可能性を削除した場合はOk(true)を返します。
     /// 既にその値になる可能性がなかった場合はOk(false)を返します。
     /// 可能性を否定した結果不能となった場合はErr(EntropyConflictError)を返します。
-    pub fn disable(&mut self, value: &Value) -> Result<bool, EntropyConflictError> {
+    pub fn disable(&mut self, value: &Value<S>) -> Result<bool, EntropyConflictError<S>> {
         if self.is_possible(value) {
             if self.len() == 1 {
                 Err(EntropyConflictError {
@@ -188,13 +220,16 @@ impl Entropy {
 
     /// 他のEntropy、またはValueと重ねあわせます。
     /// 重ねあわせが出来た場合は否定された可能性のリストを返します。
-    pub fn superimpose<T>(&mut self, into_entropy: T) -> Result<ValueIter, EntropyConflictError>
+    pub fn superimpose<T>(
+        &mut self,
+        into_entropy: T,
+    ) -> Result<ValueIter<S>, EntropyConflictError<S>>
     where
-        T: Into<Entropy>,
+        T: Into<Entropy<S>>,
     {
-        let entropy: Entropy = into_entropy.into();
+        let entropy: Entropy<S> = into_entropy.into();
         // selfから削除される予定の可能性のリスト
-        let res = ValueIter(!entropy.0 & self.0);
+        let res = ValueIter(!entropy.0 & self.0, PhantomData);
         if res.0 != self.0 {
             // 全ては削除されない場合
             self.0 &= entropy.0;
@@ -209,81 +244,84 @@ impl Entropy {
     }
 }
 
-impl Default for Entropy {
+impl<S: Size> Default for Entropy<S> {
     fn default() -> Self {
         Entropy::new()
     }
 }
 
-impl std::fmt::Display for Entropy {
+impl<S: Size> std::fmt::Display for Entropy<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
         let iter = self.clone().into_iter();
-        for v in iter {
+        for (i, v) in iter.enumerate() {
+            // 2桁の値がある場合は区切りを入れる。
+            if i != 0 && S::SIDE > 9 {
+                write!(f, ",")?;
+            }
             write!(f, "{v}")?;
         }
         write!(f, "]")
     }
 }
 
-impl TryInto<Value> for Entropy {
+impl<S: Size> TryInto<Value<S>> for Entropy<S> {
     type Error = ();
-    fn try_into(self) -> Result<Value, Self::Error> {
+    fn try_into(self) -> Result<Value<S>, Self::Error> {
         if self.len() == 1 {
-            Ok(Value(self.0))
+            Ok(Value(self.0, PhantomData))
         } else {
             Err(())
         }
     }
 }
 
-impl From<Entropy> for BITS {
-    fn from(val: Entropy) -> Self {
+impl<S: Size> From<Entropy<S>> for BITS {
+    fn from(val: Entropy<S>) -> Self {
         val.0
     }
 }
 
-impl IntoIterator for Entropy {
-    type Item = Value;
-    type IntoIter = ValueIter;
+impl<S: Size> IntoIterator for Entropy<S> {
+    type Item = Value<S>;
+    type IntoIter = ValueIter<S>;
     fn into_iter(self) -> Self::IntoIter {
-        ValueIter(self.0)
+        ValueIter(self.0, PhantomData)
     }
 }
 
-impl From<Entropy> for [u8; BITS_LENGTH] {
-    fn from(val: Entropy) -> Self {
+impl<S: Size> From<Entropy<S>> for [u8; BITS_LENGTH] {
+    fn from(val: Entropy<S>) -> Self {
         val.0.to_ne_bytes()
     }
 }
 
-impl TryFrom<[u8; BITS_LENGTH]> for Entropy {
+impl<S: Size> TryFrom<[u8; BITS_LENGTH]> for Entropy<S> {
     type Error = ();
     fn try_from(value: [u8; BITS_LENGTH]) -> Result<Self, Self::Error> {
-        let value: BITS = unsafe { std::mem::transmute(value) };
-        TryFrom::<BITS>::try_from(value)
+        TryFrom::<BITS>::try_from(BITS::from_ne_bytes(value))
     }
 }
 
-impl TryFrom<BITS> for Entropy {
+impl<S: Size> TryFrom<BITS> for Entropy<S> {
     type Error = ();
     fn try_from(value: BITS) -> Result<Self, Self::Error> {
-        if value != value & MASK {
+        if value != value & Self::MASK {
             Err(())
         } else {
-            Ok(Entropy(value))
+            Ok(Entropy(value, PhantomData))
         }
     }
 }
 
 /// エントロピーが競合した際のエラーです。
 #[derive(Debug)]
-pub struct EntropyConflictError {
-    conflicting_entropy: Entropy,
-    main_entropy: Entropy,
+pub struct EntropyConflictError<S: Size = Standard> {
+    conflicting_entropy: Entropy<S>,
+    main_entropy: Entropy<S>,
 }
 
-impl std::fmt::Display for EntropyConflictError {
+impl<S: Size> std::fmt::Display for EntropyConflictError<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} x {}", self.main_entropy, self.conflicting_entropy)
     }
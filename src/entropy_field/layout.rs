@@ -0,0 +1,85 @@
+use super::region::*;
+use super::size::*;
+
+/// 盤面で互いに同じ値の入らないブロックの構成を表します。
+/// 行と列に加え、ボックスまたは不規則な領域と、追加のブロックを持ちます。
+/// ボックスを持たない構成は、ラテン方陣のパズルに用いられます。
+#[derive(Debug, Clone)]
+pub struct Layout<S: Size = Standard> {
+    /// ボックスの代わりに用いる不規則な領域
+    regions: Option<RegionMap<S>>,
+    /// ボックスまたは不規則な領域をブロックとして扱うかどうか
+    boxes: bool,
+    /// 2本の対角線をブロックとして扱うかどうか
+    diagonals: bool,
+    /// ウィンドウの領域をブロックとして扱うかどうか
+    windows: bool,
+}
+
+impl<S: Size> Default for Layout<S> {
+    fn default() -> Self {
+        Layout::new()
+    }
+}
+
+impl<S: Size> Layout<S> {
+    /// 行、列、ボックスからなる通常の構成を返します。
+    /// ボックスを持たない大きさ(ラテン方陣)では、行と列のみの構成となります。
+    pub const fn new() -> Self {
+        Layout {
+            regions: None,
+            boxes: S::HAS_BOXES,
+            diagonals: false,
+            windows: false,
+        }
+    }
+    /// ボックスの代わりに不規則な領域を用いる構成を返します。
+    /// ボックスを持たない大きさでも、領域はブロックとして扱われます。
+    pub fn with_regions(self, regions: RegionMap<S>) -> Self {
+        Layout {
+            regions: Some(regions),
+            boxes: true,
+            ..self
+        }
+    }
+    /// ボックスを持たず、行と列のみに同じ値が入らない構成(ラテン方陣)を返します。
+    /// 不規則な領域が指定されている場合も、その領域はブロックとして扱われません。
+    pub fn without_boxes(self) -> Self {
+        Layout {
+            boxes: false,
+            ..self
+        }
+    }
+    /// 2本の対角線もブロックとして扱う構成(対角線数独)を返します。
+    pub fn with_diagonals(self) -> Self {
+        Layout {
+            diagonals: true,
+            ..self
+        }
+    }
+    /// ボックスと同じ形で、ボックスの間を1セルずつ空けて並べた
+    /// ウィンドウの領域もブロックとして扱う構成(ウィンドウ数独)を返します。
+    /// 9x9の盤面では左上が(1, 1)、(5, 1)、(1, 5)、(5, 5)の4つの領域となります。
+    pub fn with_windows(self) -> Self {
+        Layout {
+            windows: true,
+            ..self
+        }
+    }
+    /// ボックスの代わりに用いる不規則な領域を返します。
+    pub fn regions(&self) -> Option<&RegionMap<S>> {
+        self.regions.as_ref()
+    }
+    /// ボックスまたは不規則な領域をブロックとして扱うかどうかを返します。
+    pub fn boxes(&self) -> bool {
+        self.boxes
+    }
+    /// 2本の対角線をブロックとして扱うかどうかを返します。
+    pub fn diagonals(&self) -> bool {
+        self.diagonals
+    }
+    /// ウィンドウの領域をブロックとして扱うかどうかを返します。
+    pub fn windows(&self) -> bool {
+        self.windows
+    }
+}
@@ -19,22 +19,225 @@ mod test {
         a.insert(Place::new(1, 2).unwrap(), Value::TWO).unwrap();
         check!(a.clone());
     }
+    #[test]
+    fn sized_propagation() {
+        let mut field = EntropyField::<Size16>::new();
+        let place = Place::new(5, 6).unwrap();
+        field
+            .insert(place.clone(), Value::new(16).unwrap())
+            .unwrap();
+        for block in field.dependencies(&place) {
+            let mut count = 0;
+            for related_place in block {
+                count += 1;
+                if related_place != place {
+                    assert_eq!(field.entropy_at(&related_place).len(), 15);
+                }
+            }
+            assert_eq!(count, 16);
+        }
+        assert_eq!(field.entropy_at(&Place::new(8, 8).unwrap()).len(), 16);
+    }
+    #[test]
+    fn rectangular_box() {
+        let mut field = EntropyField::<Size6>::new();
+        field
+            .insert(Place::new(0, 0).unwrap(), Value::new(6).unwrap())
+            .unwrap();
+        // 横3x縦2のボックス内のセル
+        assert_eq!(field.entropy_at(&Place::new(2, 1).unwrap()).len(), 5);
+        // ボックス外のセル
+        assert_eq!(field.entropy_at(&Place::new(3, 1).unwrap()).len(), 6);
+        assert_eq!(field.entropy_at(&Place::new(1, 2).unwrap()).len(), 6);
+        let square: Vec<_> = Place::<Size6>::new(4, 3)
+            .unwrap()
+            .dependencies(&Layout::new())
+            .square()
+            .into_iter()
+            .map(|place| (place.x(), place.y()))
+            .collect();
+        assert_eq!(square, [(3, 2), (4, 2), (5, 2), (3, 3), (4, 3), (5, 3)]);
+    }
+    #[test]
+    fn latin_square() {
+        let mut field = EntropyField::<Latin<4>>::new();
+        assert!(!field.layout().boxes());
+        let place = Place::new(1, 0).unwrap();
+        assert_eq!(field.dependencies(&place).into_iter().count(), 2);
+        field.insert(place, Value::new(4).unwrap()).unwrap();
+        // 同じ行にも列にもないセル
+        assert_eq!(field.entropy_at(&Place::new(0, 1).unwrap()).len(), 4);
+        assert!(
+            Place::<Latin<4>>::new(1, 1)
+                .unwrap()
+                .window_origin()
+                .is_none()
+        );
+    }
+    #[test]
+    fn irregular_regions() {
+        let regions: RegionMap<Size4> = "aaab\nacbb\ncccb\ndddd".parse().unwrap();
+        let mut field = EntropyField::with_regions(regions);
+        field
+            .insert(Place::new(1, 1).unwrap(), Value::new(4).unwrap())
+            .unwrap();
+        // 同じ領域に属するが、ボックスは異なるセル
+        assert_eq!(field.entropy_at(&Place::new(2, 2).unwrap()).len(), 3);
+        // ボックスは同じだが、領域が異なるセル
+        assert_eq!(field.entropy_at(&Place::new(0, 0).unwrap()).len(), 4);
+    }
+    #[test]
+    fn diagonals() {
+        let mut field = EntropyField::with_layout(Layout::new().with_diagonals());
+        field.insert(Place::new(0, 0).unwrap(), Value::ONE).unwrap();
+        assert!(
+            !field
+                .entropy_at(&Place::new(8, 8).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        assert!(
+            field
+                .entropy_at(&Place::new(2, 6).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        // 中央のセルは2本の対角線の両方に属する。
+        let center = Place::new(4, 4).unwrap();
+        assert_eq!(field.dependencies(&center).into_iter().count(), 5);
+        let dependencies = center.dependencies(field.layout());
+        assert!(dependencies.diagonal().is_some() && dependencies.anti_diagonal().is_some());
+        // 対角線上で2が入る可能性のあるセルを1つに絞ると、そのセルが収束する。
+        for i in 1..8 {
+            let place = Place::new(i, i).unwrap();
+            let mut entropy = Entropy::new();
+            entropy.disable(&Value::TWO).unwrap();
+            field.insert(place, entropy).unwrap();
+        }
+        assert_eq!(
+            field.entropy_at(&Place::new(8, 8).unwrap()),
+            &Value::TWO.into()
+        );
+    }
+    #[test]
+    fn windows() {
+        let mut field = EntropyField::with_layout(Layout::new().with_windows());
+        field.insert(Place::new(1, 1).unwrap(), Value::ONE).unwrap();
+        // (1, 1)から(3, 3)までのウィンドウは、3つのボックスにまたがる。
+        assert!(
+            !field
+                .entropy_at(&Place::new(3, 3).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        assert!(
+            field
+                .entropy_at(&Place::new(4, 4).unwrap())
+                .is_possible(&Value::ONE)
+        );
+        // ウィンドウの間のセルはウィンドウに属さない。
+        let gap = Place::new(4, 1).unwrap();
+        assert_eq!(field.dependencies(&gap).into_iter().count(), 3);
+        // ウィンドウ内のセルは、行、列、ボックスに加えてウィンドウに属する。
+        let corner = Place::new(7, 7).unwrap();
+        assert_eq!(corner.dependencies(field.layout()).into_iter().count(), 4);
+        let window: Vec<_> = corner
+            .dependencies(field.layout())
+            .window()
+            .unwrap()
+            .into_iter()
+            .map(|place| (place.x(), place.y()))
+            .collect();
+        assert_eq!(window[0], (5, 5));
+        assert_eq!(window[8], (7, 7));
+    }
+    #[test]
+    fn custom_constraint() {
+        /// 2つのセルが同じ値になるルール
+        #[derive(Debug)]
+        struct Equal(Place, Place);
+        impl std::fmt::Display for Equal {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{} = {}", self.0, self.1)
+            }
+        }
+        impl Constraint for Equal {
+            fn on_shrink(
+                &self,
+                cx: &mut Propagator<'_>,
+                place: &Place,
+                _: ValueIter,
+            ) -> Result<(), RuleViolationError> {
+                let other = if place == &self.0 {
+                    &self.1
+                } else if place == &self.1 {
+                    &self.0
+                } else {
+                    return Ok(());
+                };
+                let entropy = cx.entropy_at(place).to_owned();
+                cx.superimpose(other, entropy)?;
+                Ok(())
+            }
+        }
+        let mut field = EntropyField::new();
+        field.insert(Place::new(0, 0).unwrap(), Value::ONE).unwrap();
+        field
+            .add_constraint(Equal(Place::new(0, 0).unwrap(), Place::new(4, 4).unwrap()))
+            .unwrap();
+        assert_eq!(
+            field.entropy_at(&Place::new(4, 4).unwrap()),
+            &Value::ONE.into()
+        );
+        // (4, 4)が1になったことで、その行からも1が否定される。
+        assert!(
+            !field
+                .entropy_at(&Place::new(8, 4).unwrap())
+                .is_possible(&Value::ONE)
+        );
+
+        field.insert(Place::new(8, 8).unwrap(), Value::ONE).unwrap();
+        let before = field.clone();
+        let error = field
+            .add_constraint(Equal(Place::new(8, 8).unwrap(), Place::new(8, 7).unwrap()))
+            .unwrap_err();
+        assert_eq!(error.rule().unwrap().to_string(), "(8, 8) = (8, 7)");
+        // ルール違反となった場合、ルールの追加も途中までの伝播も盤面に残らない。
+        assert_eq!(field.constraints().count(), 2);
+        for i in 0..81 {
+            let place = Place::new_from_raw(i).unwrap();
+            assert_eq!(field.entropy_at(&place), before.entropy_at(&place));
+        }
+    }
 }
 
+pub mod constraint;
 pub mod entropy;
+pub mod layout;
 pub mod place;
+pub mod region;
+pub mod size;
 
+use constraint::*;
 use entropy::*;
+use layout::*;
 use place::*;
+use region::*;
+use size::*;
+use std::rc::Rc;
 
-/// セルの個数
-pub const CELLS_COUNT: usize = 81;
+/// 通常の9x9の盤面のセルの個数
+pub const CELLS_COUNT: usize = Standard::CELLS;
 
 /// 数独の表上で演繹的にエントロピーの重ねあわせを計算する構造体です。
+/// 盤面の大きさは型引数Sで指定します。
 #[derive(Clone)]
-pub struct EntropyField([Entropy; CELLS_COUNT]);
+pub struct EntropyField<S: Size = Standard> {
+    cells: Vec<Entropy<S>>,
+    /// 互いに同じ値の入らないブロックの構成
+    layout: Rc<Layout<S>>,
+    /// 伝播に参加するルール
+    constraints: Rc<Vec<Rc<dyn Constraint<S>>>>,
+}
 
-impl Default for EntropyField {
+impl<S: Size> Default for EntropyField<S> {
     fn default() -> Self {
         EntropyField::new()
     }
@@ -44,37 +247,42 @@ pub const BITS_LENGTH: usize = std::mem::size_of::<BITS>() * CELLS_COUNT;
 
 impl From<EntropyField> for [u8; BITS_LENGTH] {
     fn from(val: EntropyField) -> Self {
-        unsafe { std::mem::transmute(val.0) }
+        let mut bytes = [0; BITS_LENGTH];
+        for (chunk, cell) in bytes.chunks_exact_mut(entropy::BITS_LENGTH).zip(val.cells) {
+            let cell: [u8; entropy::BITS_LENGTH] = cell.into();
+            chunk.copy_from_slice(&cell);
+        }
+        bytes
     }
 }
 
 impl TryFrom<[u8; BITS_LENGTH]> for EntropyField {
     type Error = ();
     fn try_from(value: [u8; BITS_LENGTH]) -> Result<Self, Self::Error> {
-        let cells: [BITS; CELLS_COUNT] = unsafe { std::mem::transmute(value) };
-        for cell in &cells {
-            let bytes: [u8; entropy::BITS_LENGTH] = cell.to_owned().to_ne_bytes();
-            if Entropy::try_from(bytes).is_err() {
-                return Err(());
-            }
+        let mut cells = Vec::with_capacity(CELLS_COUNT);
+        for chunk in value.chunks_exact(entropy::BITS_LENGTH) {
+            let bytes: [u8; entropy::BITS_LENGTH] = chunk.try_into().unwrap();
+            cells.push(Entropy::try_from(bytes)?);
         }
-        Ok(EntropyField(unsafe {
-            std::mem::transmute::<[u32; 81], [Entropy; 81]>(cells)
-        }))
+        let mut field = EntropyField::new();
+        field.cells = cells;
+        Ok(field)
     }
 }
 
-impl std::fmt::Display for EntropyField {
+impl<S: Size> std::fmt::Display for EntropyField<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..81 {
-            let entropy = &self.0[i];
+        // 値やエントロピーの大きさを表示する桁数
+        let width = S::SIDE.to_string().len();
+        for i in 0..S::CELLS {
+            let entropy = &self.cells[i];
             if let Ok(value) = entropy.to_owned().try_into() {
-                let value: Value = value;
-                write!(f, " {} ", value)?;
+                let value: Value<S> = value;
+                write!(f, " {:>width$} ", value.to_string())?;
             } else {
-                write!(f, "[{}]", entropy.len())?;
+                write!(f, "[{:>width$}]", entropy.len())?;
             }
-            if i % 9 == 8 && i != 80 {
+            if i % S::SIDE == S::SIDE - 1 && i != S::CELLS - 1 {
                 writeln!(f)?;
             }
         }
@@ -82,143 +290,154 @@ impl std::fmt::Display for EntropyField {
     }
 }
 
-impl EntropyField {
-    /// 新しいEntropyFieldを返します。
-    pub const fn new() -> Self {
-        const INITIAL_ENTROPY: Entropy = Entropy::new();
-        EntropyField([INITIAL_ENTROPY; CELLS_COUNT])
+impl<S: Size> EntropyField<S> {
+    /// 数独の基本ルールに従う新しいEntropyFieldを返します。
+    pub fn new() -> Self {
+        EntropyField {
+            cells: vec![Entropy::new(); S::CELLS],
+            layout: Rc::new(Layout::new()),
+            constraints: Rc::new(vec![Rc::new(SudokuRule)]),
+        }
+    }
+    /// 指定された構成の盤面で、数独の基本ルールに従う新しいEntropyFieldを返します。
+    pub fn with_layout(layout: Layout<S>) -> Self {
+        EntropyField {
+            layout: Rc::new(layout),
+            ..EntropyField::new()
+        }
+    }
+    /// ボックスを持たず、行と列のみに同じ値が入らない新しいEntropyFieldを返します。
+    /// ボックスを持つ大きさの盤面を、ラテン方陣のパズルに用いる場合に使います。
+    /// `Latin`の大きさでは、`new`がはじめからボックスを持たない盤面を返します。
+    pub fn latin() -> Self {
+        EntropyField::with_layout(Layout::new().without_boxes())
+    }
+    /// ボックスの代わりに不規則な領域を用いる新しいEntropyFieldを返します。
+    pub fn with_regions(regions: RegionMap<S>) -> Self {
+        EntropyField::with_layout(Layout::new().with_regions(regions))
+    }
+    /// 盤面の構成を返します。
+    pub fn layout(&self) -> &Layout<S> {
+        &self.layout
+    }
+    /// ボックスの代わりに用いている不規則な領域を返します。
+    pub fn regions(&self) -> Option<&RegionMap<S>> {
+        self.layout.regions()
+    }
+    /// この盤面において、指定された位置に直接的に影響のあるPlaceを返します。
+    pub fn dependencies<'a>(&'a self, place: &'a Place<S>) -> Dependencies<'a, S> {
+        place.dependencies(&self.layout)
+    }
+    /// 伝播に参加しているルールを返します。
+    pub fn constraints(&self) -> impl Iterator<Item = &dyn Constraint<S>> {
+        self.constraints
+            .iter()
+            .map(|constraint| constraint.as_ref())
+    }
+    /// 伝播に参加するルールを追加します。
+    /// 追加したルールに従って現在の盤面から伝播を行い、
+    /// ルール違反が検出された場合はErr(RuleViolationError)を返します。
+    /// その場合、ルールは追加されず、盤面も変更されません。
+    pub fn add_constraint(
+        &mut self,
+        constraint: impl Constraint<S> + 'static,
+    ) -> Result<(), RuleViolationError<S>> {
+        let constraint: Rc<dyn Constraint<S>> = Rc::new(constraint);
+        // 途中までの伝播が残らないよう、複製に適用してから反映する。
+        let mut cells = self.cells.clone();
+        let mut constraints = self.constraints.clone();
+        Rc::make_mut(&mut constraints).push(constraint.clone());
+        let mut cx = Propagator::new(&mut cells, &self.layout);
+        constraint
+            .init(&mut cx)
+            .map_err(|err| err.caused_by(&constraint))?;
+        Self::propagate(&mut cx, &constraints)?;
+        self.cells = cells;
+        self.constraints = constraints;
+        Ok(())
     }
     /// 現在確認できたエントロピーの総量を返します。
     pub fn len(&self) -> f64 {
         let mut count = 1f64;
-        for i in 0..81 {
-            count *= self.0[i].len() as f64
+        for i in 0..S::CELLS {
+            count *= self.cells[i].len() as f64
         }
         count
     }
 
     /// 指定された位置のエントロピーを返します。
-    pub fn entropy_at(&self, place: &Place) -> &Entropy {
-        &self.0[*place.raw()]
+    pub fn entropy_at(&self, place: &Place<S>) -> &Entropy<S> {
+        &self.cells[*place.raw()]
     }
 
     /// 指定された位置のセルにエントロピーを適用します。
     pub fn insert(
         &mut self,
-        place: Place,
-        into_entropy: impl Into<Entropy>,
-    ) -> Result<(), RuleViolationError> {
-        let mut remaining_sets: Vec<(Entropy, Place)> = vec![(into_entropy.into(), place)];
-        while !remaining_sets.is_empty() {
-            let iter = remaining_sets;
-            remaining_sets = Default::default();
-            for (value, place) in iter {
-                for remaining_set in self.inner_insert(value, place)? {
-                    remaining_sets.push((remaining_set.0.into(), remaining_set.1));
-                }
-            }
-        }
-        Ok(())
+        place: Place<S>,
+        into_entropy: impl Into<Entropy<S>>,
+    ) -> Result<(), RuleViolationError<S>> {
+        let mut cx = Propagator::new(&mut self.cells, &self.layout);
+        cx.superimpose(&place, into_entropy)?;
+        Self::propagate(&mut cx, &self.constraints)
     }
-    /// 指定されたセルにエントロピーを適用します。
-    /// 新たに必要になった収束先と値のセットを返します。
-    /// この実装になったのはスタックオーバーフロー対策の為。
-    fn inner_insert(
-        &mut self,
-        into_entropy: impl Into<Entropy>,
-        place: Place,
-    ) -> Result<Vec<(Value, Place)>, RuleViolationError> {
-        let entropy = into_entropy.into();
-        let mut remaining_sets = Vec::new();
-        macro_rules! entropy {
-            ($place: expr) => {
-                self.0[$place.raw().to_owned()]
-            };
-        }
-        /// セルの値を1つ否定する度に呼ぶ。
-        /// そのセルに関係するセルが唯一の値になる可能性があるので、
-        /// その値について唯一の可能性の位置となったセルを収束させる。
-        macro_rules! search_uniqueness_around {
-            ($disabled_value: expr, $changing_place: expr) => {{
-                let disabled_value: &Value = $disabled_value;
-                let changing_place: &Place = $changing_place;
-                for block in changing_place.dependencies().into_iter() {
-                    let mut first: Option<Place> = None;
-                    for affected_place in block {
-                        // 与えられた一列(y_line)、一行(x_line)、一区画(square)
-                        // (:block)のうちで、与えられた$valueが唯一のものを探す。
-                        if &affected_place != changing_place {
-                            if entropy!(affected_place).is_possible(disabled_value) {
-                                match first {
-                                    Some(_) => {
-                                        // まだ複数のセルで可能性がある。
-                                        first = None;
-                                        break;
-                                    },
-                                    None => {
-                                        // 可能性のある最初のセル
-                                        first = Some(affected_place);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    if let Some(unique_place) = first {
-                        remaining_sets.push((disabled_value.to_owned(), unique_place));
-                    }
-                }
-            }};
-        }
-        // 指定されたセルのエントロピーを収束させる。
-        let disabled_values =
-            entropy!(&place)
-                .superimpose(entropy)
-                .map_err(|err| RuleViolationError {
-                    conflict: err,
-                    place: place.to_owned(),
-                })?;
-        for disabled_value in disabled_values {
-            // 削除された可能性について探索
-            search_uniqueness_around!(&disabled_value, &place);
-        }
-        // 仮に指定されたエントロピーが収束する場合。
-        // (値が一つのとき)
-        if let Ok(value) = entropy!(place).to_owned().try_into() {
-            for related_block in place.dependencies().into_iter() {
-                for related_place in related_block.into_iter() {
-                    if related_place != place
-                        && entropy!(related_place).disable(&value).map_err(|err| {
-                            RuleViolationError {
-                                conflict: err,
-                                place: place.to_owned(),
-                            }
-                        })?
-                    {
-                        search_uniqueness_around!(&value, &related_place);
-
-                        // 仮にこの削除によって関係するセルの可能性の数が1つになった場合
-                        if let Ok(value) = entropy!(&related_place).to_owned().try_into() {
-                            remaining_sets.push((value, related_place));
-                        }
-                    }
-                }
+    /// エントロピーが縮小したセルを順に各ルールへ通知し、
+    /// 新たな縮小がなくなるまで伝播を続けます。
+    /// 再帰せずに変更を順に処理するのはスタックオーバーフロー対策の為。
+    fn propagate(
+        cx: &mut Propagator<'_, S>,
+        constraints: &[Rc<dyn Constraint<S>>],
+    ) -> Result<(), RuleViolationError<S>> {
+        while let Some((place, disabled)) = cx.pop_change() {
+            for constraint in constraints {
+                constraint
+                    .on_shrink(cx, &place, disabled.clone())
+                    .map_err(|err| err.caused_by(constraint))?;
             }
         }
-        // 直接関係のあるセルから可能性を削除していく。
-        Ok(remaining_sets)
+        Ok(())
     }
 }
 
 #[derive(Debug)]
 /// ルール違反が検出されたエラー
-pub struct RuleViolationError {
-    conflict: EntropyConflictError,
-    place: Place,
+pub struct RuleViolationError<S: Size = Standard> {
+    conflict: EntropyConflictError<S>,
+    place: Place<S>,
+    /// 違反を検出したルール
+    rule: Option<Rc<dyn Constraint<S>>>,
 }
 
-impl std::fmt::Display for RuleViolationError {
+impl<S: Size> RuleViolationError<S> {
+    fn new(conflict: EntropyConflictError<S>, place: Place<S>) -> Self {
+        RuleViolationError {
+            conflict,
+            place,
+            rule: None,
+        }
+    }
+    /// 違反を検出したルールが未設定であれば設定します。
+    fn caused_by(mut self, rule: &Rc<dyn Constraint<S>>) -> Self {
+        self.rule.get_or_insert_with(|| rule.clone());
+        self
+    }
+    /// 競合が発生したセルの位置を返します。
+    pub fn place(&self) -> &Place<S> {
+        &self.place
+    }
+    /// 違反を検出したルールを返します。
+    /// 直接指定したエントロピーが競合した場合はNoneを返します。
+    pub fn rule(&self) -> Option<&dyn Constraint<S>> {
+        self.rule.as_deref()
+    }
+}
+
+impl<S: Size> std::fmt::Display for RuleViolationError<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.conflict.fmt(f)?;
-        write!(f, " @{}", self.place)
+        write!(f, " @{}", self.place)?;
+        if let Some(rule) = &self.rule {
+            write!(f, " ({rule})")?;
+        }
+        Ok(())
     }
 }
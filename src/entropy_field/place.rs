@@ -1,193 +1,529 @@
+use super::layout::*;
+use super::size::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
 /// 数独上の位置を表します。
 #[derive(PartialEq, Eq, Clone, Debug)]
-pub struct Place(usize);
+pub struct Place<S: Size = Standard>(usize, PhantomData<S>);
 
-impl Place {
+impl<S: Size> Place<S> {
     /// PlaceのX座標を返します。
     pub fn x(&self) -> usize {
-        self.0 % 9
+        self.0 % S::SIDE
     }
     /// PlaceのY座標を返します。
     pub fn y(&self) -> usize {
-        self.0 / 9
+        self.0 / S::SIDE
     }
     /// 先頭から数えたインデックスをそのまま返します。
     pub fn raw(&self) -> &usize {
         &self.0
     }
     /// 新しいPlaceを返します。
-    /// 引数の値が0以上9未満でない場合はNoneが返ります。
-    pub const fn new(x: usize, y: usize) -> Option<Place> {
-        if x < 9 && y < 9 {
-            Some(Place(y * 9 + x))
+    /// 引数の値が0以上、盤面の一辺のセル数未満でない場合はNoneが返ります。
+    pub const fn new(x: usize, y: usize) -> Option<Self> {
+        if x < S::SIDE && y < S::SIDE {
+            Some(Place(y * S::SIDE + x, PhantomData))
         } else {
             None
         }
     }
     /// 新しいPlaceを返します。
     /// そのX、Yの値が範囲内にあるかどうかの確認をしません。
-    pub const unsafe fn new_unchecked(x: usize, y: usize) -> Place {
-        Place(y * 9 + x)
+    ///
+    /// # Safety
+    /// X、Yの値が0以上、盤面の一辺のセル数未満である必要があります。
+    pub const unsafe fn new_unchecked(x: usize, y: usize) -> Self {
+        Place(y * S::SIDE + x, PhantomData)
     }
     /// 新しいPlaceを返します。
     /// そのインデックスの値が範囲外であればNoneが返ります。。
-    pub const fn new_from_raw(i: usize) -> Option<Place> {
-        if i < 81 { Some(Place(i)) } else { None }
+    pub const fn new_from_raw(i: usize) -> Option<Self> {
+        if i < S::CELLS {
+            Some(Place(i, PhantomData))
+        } else {
+            None
+        }
     }
     /// 新しいPlaceを返します。
     /// そのインデックスの値が範囲内にあるかどうかの確認をしません。
-    pub const unsafe fn new_from_raw_unchecked(i: usize) -> Place {
-        Place(i)
+    ///
+    /// # Safety
+    /// インデックスの値がセルの個数未満である必要があります。
+    pub const unsafe fn new_from_raw_unchecked(i: usize) -> Self {
+        Place(i, PhantomData)
+    }
+    /// 指定された構成の盤面において、そのPlaceに直接的に影響のあるPlaceを返します。
+    /// 対角線やウィンドウなどの追加のブロックは、構成で有効な場合にのみ含まれます。
+    pub fn dependencies<'a>(&'a self, layout: &'a Layout<S>) -> Dependencies<'a, S> {
+        Dependencies(self, layout)
+    }
+    /// Placeが左上から右下への対角線上にあるかどうかを返します。
+    pub fn is_on_diagonal(&self) -> bool {
+        self.x() == self.y()
+    }
+    /// Placeがウィンドウの領域にある場合に、その領域の左上のPlaceを返します。
+    /// ウィンドウはボックスと同じ形で、盤面の端とボックスの間を1セルずつ空けて並びます。
+    /// ボックスを持たない大きさではウィンドウもないため、常にNoneを返します。
+    pub fn window_origin(&self) -> Option<Self> {
+        if !S::HAS_BOXES {
+            return None;
+        }
+        fn start(i: usize, width: usize, side: usize) -> Option<usize> {
+            let offset = i.checked_sub(1)? % (width + 1);
+            let start = i - offset;
+            (offset < width && start + width < side).then_some(start)
+        }
+        let x = start(self.x(), S::BOX_WIDTH, S::SIDE)?;
+        let y = start(self.y(), S::BOX_HEIGHT, S::SIDE)?;
+        Place::new(x, y)
+    }
+    /// Placeが右上から左下への対角線上にあるかどうかを返します。
+    pub fn is_on_anti_diagonal(&self) -> bool {
+        self.x() + self.y() == S::SIDE - 1
+    }
+}
+
+/// 斜めに進む向きを表します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slant {
+    /// 右下へ進む。
+    DownRight,
+    /// 左下へ進む。
+    DownLeft,
+    /// 右上へ進む。
+    UpRight,
+    /// 左上へ進む。
+    UpLeft,
+}
+
+impl Slant {
+    /// 1回進む際のX座標とY座標の変化量を返します。
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Slant::DownRight => (1, 1),
+            Slant::DownLeft => (-1, 1),
+            Slant::UpRight => (1, -1),
+            Slant::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl std::fmt::Display for Slant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slant::DownRight => write!(f, "右下"),
+            Slant::DownLeft => write!(f, "左下"),
+            Slant::UpRight => write!(f, "右上"),
+            Slant::UpLeft => write!(f, "左上"),
+        }
     }
-    /// そのPlaceに直接的に影響のあるPlaceを返します。
-    pub fn dependencies(&self) -> Dependencies<'_> {
-        Dependencies(self)
+}
+
+impl<S: Size> Place<S> {
+    /// そのPlaceから指定された向きに、盤面の端まで斜めに進むセルを返します。
+    /// 返されるブロックのセルの個数は、開始するPlaceと向きによって異なります。
+    pub fn slant(&self, direction: Slant) -> block::Block<S> {
+        unsafe { block::Block::new_unchecked(self.0, block::BlockType::Slant(direction)) }
+    }
+}
+
+impl<S: Size> Place<S> {
+    /// チェスのナイトの動きで移動できるPlaceを返します。
+    pub fn knight_moves(&self) -> Neighbors<S> {
+        const KNIGHT: [(isize, isize); 8] = [
+            (1, 2),
+            (2, 1),
+            (2, -1),
+            (1, -2),
+            (-1, -2),
+            (-2, -1),
+            (-2, 1),
+            (-1, 2),
+        ];
+        Neighbors {
+            center: self.clone(),
+            offsets: &KNIGHT,
+        }
+    }
+    /// チェスのキングの動きで移動できるPlaceを返します。
+    pub fn king_moves(&self) -> Neighbors<S> {
+        const KING: [(isize, isize); 8] = [
+            (0, 1),
+            (1, 1),
+            (1, 0),
+            (1, -1),
+            (0, -1),
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+        ];
+        Neighbors {
+            center: self.clone(),
+            offsets: &KING,
+        }
+    }
+    /// 縦横に隣接するPlaceを返します。
+    pub fn orthogonal_neighbors(&self) -> Neighbors<S> {
+        const ORTHOGONAL: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+        Neighbors {
+            center: self.clone(),
+            offsets: &ORTHOGONAL,
+        }
+    }
+}
+
+/// あるPlaceから一定の移動で到達できる、盤面内のPlaceのイテレータです。
+#[derive(Debug, Clone)]
+pub struct Neighbors<S: Size = Standard> {
+    center: Place<S>,
+    /// 残りの移動量の(x, y)
+    offsets: &'static [(isize, isize)],
+}
+
+impl<S: Size> Iterator for Neighbors<S> {
+    type Item = Place<S>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(((dx, dy), rest)) = self.offsets.split_first() {
+            self.offsets = rest;
+            let x = self.center.x().checked_add_signed(*dx);
+            let y = self.center.y().checked_add_signed(*dy);
+            if let Some(place) = x.zip(y).and_then(|(x, y)| Place::new(x, y)) {
+                return Some(place);
+            }
+        }
+        None
     }
 }
 
-impl std::fmt::Display for Place {
+impl<S: Size> std::fmt::Display for Place<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x(), self.y())
     }
 }
 
-/// 関係するブロックを表します。
+/// 盤面の構成に従って、関係するブロックを表します。
 #[derive(Debug, Clone)]
-pub struct Dependencies<'a>(&'a Place);
+pub struct Dependencies<'a, S: Size = Standard>(&'a Place<S>, &'a Layout<S>);
 
-impl Dependencies<'_> {
+impl<S: Size> Dependencies<'_, S> {
     /// 何のPlaceに関するDependenciesかを返します。
-    pub fn about(&self) -> &Place {
+    pub fn about(&self) -> &Place<S> {
         self.0
     }
     /// 横の1行のラインの依存セルを返します。
-    pub fn x_line(&self) -> block::Block {
-        unsafe { block::Block::new_unchecked(self.0.0 / 9 * 9, block::BlockType::XLine) }
+    pub fn x_line(&self) -> block::Block<S> {
+        unsafe {
+            block::Block::new_unchecked(self.0.0 / S::SIDE * S::SIDE, block::BlockType::XLine)
+        }
     }
     /// 縦の1列のラインの依存セルを返します。
-    pub fn y_line(&self) -> block::Block {
-        unsafe { block::Block::new_unchecked(self.0.0 % 9, block::BlockType::YLine) }
+    pub fn y_line(&self) -> block::Block<S> {
+        unsafe { block::Block::new_unchecked(self.0.0 % S::SIDE, block::BlockType::YLine) }
     }
-    /// 3x3の領域の依存セルを返します。
-    pub fn square(&self) -> block::Block {
+    /// ボックスの領域の依存セルを返します。
+    pub fn square(&self) -> block::Block<S> {
         let (x, y) = (self.0.x(), self.0.y());
         unsafe {
-            block::Block::new_unchecked((y / 3) * 27 + ((x / 3) * 3), block::BlockType::Square)
+            block::Block::new_unchecked(
+                (y / S::BOX_HEIGHT) * S::BOX_HEIGHT * S::SIDE + ((x / S::BOX_WIDTH) * S::BOX_WIDTH),
+                block::BlockType::Square,
+            )
+        }
+    }
+    /// そのPlaceが属する領域の依存セルを返します。
+    /// 不規則な領域が指定されていない場合はボックスの領域を返します。
+    pub fn region(&self) -> block::Block<S> {
+        match self.1.regions() {
+            Some(regions) => unsafe {
+                let cells = regions.cells(regions.region_of(self.0));
+                block::Block::new_unchecked(cells[0], block::BlockType::Region(cells.clone()))
+            },
+            None => self.square(),
+        }
+    }
+    /// 対角線がブロックとして扱われ、Placeがその上にある場合に、
+    /// 左上から右下への対角線の依存セルを返します。
+    pub fn diagonal(&self) -> Option<block::Block<S>> {
+        if self.1.diagonals() && self.0.is_on_diagonal() {
+            Some(unsafe { block::Block::new_unchecked(0, block::BlockType::Diagonal) })
+        } else {
+            None
+        }
+    }
+    /// 対角線がブロックとして扱われ、Placeがその上にある場合に、
+    /// 右上から左下への対角線の依存セルを返します。
+    pub fn anti_diagonal(&self) -> Option<block::Block<S>> {
+        if self.1.diagonals() && self.0.is_on_anti_diagonal() {
+            Some(unsafe {
+                block::Block::new_unchecked(S::SIDE - 1, block::BlockType::AntiDiagonal)
+            })
+        } else {
+            None
+        }
+    }
+    /// ウィンドウの領域がブロックとして扱われ、Placeがその中にある場合に、
+    /// ウィンドウの領域の依存セルを返します。
+    pub fn window(&self) -> Option<block::Block<S>> {
+        if !self.1.windows() {
+            return None;
         }
+        let origin = self.0.window_origin()?;
+        Some(unsafe { block::Block::new_unchecked(origin.0, block::BlockType::Window) })
     }
 }
 
-impl<'a> IntoIterator for Dependencies<'a> {
-    type Item = block::Block;
-    type IntoIter = BlockIter<'a>;
+impl<'a, S: Size> IntoIterator for Dependencies<'a, S> {
+    type Item = block::Block<S>;
+    type IntoIter = BlockIter<'a, S>;
     fn into_iter(self) -> Self::IntoIter {
         BlockIter(self, 0)
     }
 }
 
-/// ある1つのセルに関係する3つのブロックのイテレータです。
-pub struct BlockIter<'a>(Dependencies<'a>, usize);
+/// ある1つのセルに関係するブロックのイテレータです。
+/// 行、列、ボックス(または領域)の3つに加え、
+/// 盤面の構成によっては追加のブロックを返します。
+/// ボックスを持たない構成では、ボックスと領域は返しません。
+pub struct BlockIter<'a, S: Size = Standard>(Dependencies<'a, S>, usize);
 
-impl Iterator for BlockIter<'_> {
-    type Item = block::Block;
+impl<S: Size> Iterator for BlockIter<'_, S> {
+    type Item = block::Block<S>;
     fn next(&mut self) -> Option<Self::Item> {
-        let res = match self.1 {
-            0 => Some(self.0.x_line()),
-            1 => Some(self.0.y_line()),
-            2 => Some(self.0.square()),
-            _ => None,
-        };
-        self.1 += 1;
-        res
+        loop {
+            let res = match self.1 {
+                0 => Some(self.0.x_line()),
+                1 => Some(self.0.y_line()),
+                2 => self.0.1.boxes().then(|| self.0.region()),
+                3 => self.0.diagonal(),
+                4 => self.0.anti_diagonal(),
+                5 => self.0.window(),
+                _ => return None,
+            };
+            self.1 += 1;
+            if res.is_some() {
+                return res;
+            }
+        }
     }
 }
 
 mod block {
     use super::*;
-    /// 互いに関係のあるPlaceの9セル1組がいずれのタイプかを表します。
+    /// 互いに関係のあるPlaceの1組がいずれのタイプかを表します。
     pub enum BlockType {
-        /// 横一行の9セル
+        /// 横一行のセル
         XLine,
-        /// 縦一列の9セル
+        /// 縦一列のセル
         YLine,
-        /// 3x3の正方形9セル
+        /// ボックスの長方形のセル
         Square,
+        /// 左上から右下への対角線のセル
+        Diagonal,
+        /// 右上から左下への対角線のセル
+        AntiDiagonal,
+        /// ボックスの間に置かれたウィンドウの長方形のセル
+        Window,
+        /// 指定されたセルから盤面の端までの斜めのセル
+        Slant(Slant),
+        /// 不規則な領域のセル
+        /// 領域に属するセルのインデックスを昇順に保持します。
+        Region(Rc<[usize]>),
     }
 
-    /// 互いに関係のあるPlaceの9セル1組を表します。
+    /// 互いに関係のあるPlaceの1組を表します。
+    /// 1組のセルの個数は、斜めのセルを除いて盤面の一辺のセル数と等しくなります。
     // Block.0は最初に指し示すPlaceの値
-    pub struct Block(usize, BlockType);
+    pub struct Block<S: Size = Standard>(usize, BlockType, PhantomData<S>);
 
-    impl Block {
+    impl<S: Size> Block<S> {
         /// 直接ブロックを構築して返します。
-        pub unsafe fn new_unchecked(i: usize, blocktype: BlockType) -> Block {
-            Block(i, blocktype)
+        ///
+        /// # Safety
+        /// iはそのブロックのタイプで最初に指し示すPlaceの値である必要があります。
+        pub unsafe fn new_unchecked(i: usize, blocktype: BlockType) -> Self {
+            Block(i, blocktype, PhantomData)
         }
     }
 
-    impl IntoIterator for Block {
-        type Item = Place;
-        type IntoIter = PlaceIter;
+    impl<S: Size> IntoIterator for Block<S> {
+        type Item = Place<S>;
+        type IntoIter = PlaceIter<S>;
         fn into_iter(self) -> Self::IntoIter {
             use BlockType::*;
             match self.1 {
                 XLine => PlaceIter::x_line(self.0),
                 YLine => PlaceIter::y_line(self.0),
                 Square => PlaceIter::square(self.0),
+                Window => PlaceIter::window(self.0),
+                Slant(direction) => PlaceIter::slant(self.0, direction),
+                Diagonal => PlaceIter::diagonal(self.0),
+                AntiDiagonal => PlaceIter::anti_diagonal(self.0),
+                Region(cells) => PlaceIter::region(cells),
             }
         }
     }
     /// Placeを返すイテレータです。
-    /// 互いに関係のある9セル内でイテレートします。
-    pub struct PlaceIter {
-        place: usize,
-        sneak: fn(usize) -> usize,
-        len: usize,
+    /// 互いに関係のあるセル内でイテレートします。
+    pub struct PlaceIter<S: Size = Standard>(Cursor, PhantomData<S>);
+
+    /// PlaceIterが次に指し示すセルを求める方法です。
+    enum Cursor {
+        /// 一定の規則で次のセルへ進む。
+        Sneak {
+            place: usize,
+            sneak: fn(usize) -> usize,
+            len: usize,
+        },
+        /// セルのインデックスの列を順に辿る。
+        List(Rc<[usize]>, usize),
     }
 
-    impl PlaceIter {
-        fn x_line(place: usize) -> PlaceIter {
+    impl<S: Size> PlaceIter<S> {
+        fn x_line(place: usize) -> Self {
             fn sneak(index: usize) -> usize {
                 index + 1
             }
-            PlaceIter {
-                place,
-                sneak,
-                len: 9,
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak,
+                    len: S::SIDE,
+                },
+                PhantomData,
+            )
+        }
+        fn y_line(place: usize) -> Self {
+            fn sneak<S: Size>(index: usize) -> usize {
+                index + S::SIDE
             }
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak: sneak::<S>,
+                    len: S::SIDE,
+                },
+                PhantomData,
+            )
         }
-        fn y_line(place: usize) -> PlaceIter {
-            fn sneak(index: usize) -> usize {
-                index + 9
+        fn square(place: usize) -> Self {
+            fn sneak<S: Size>(index: usize) -> usize {
+                index
+                    + if index % S::BOX_WIDTH == S::BOX_WIDTH - 1 {
+                        S::SIDE - S::BOX_WIDTH + 1
+                    } else {
+                        1
+                    }
             }
-            PlaceIter {
-                place,
-                sneak,
-                len: 9,
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak: sneak::<S>,
+                    len: S::SIDE,
+                },
+                PhantomData,
+            )
+        }
+        fn window(place: usize) -> Self {
+            fn sneak<S: Size>(index: usize) -> usize {
+                // ウィンドウの右端の列は、ボックスの幅に1を足した値で割った余りがボックスの幅となる。
+                index
+                    + if index % S::SIDE % (S::BOX_WIDTH + 1) == S::BOX_WIDTH {
+                        S::SIDE - S::BOX_WIDTH + 1
+                    } else {
+                        1
+                    }
             }
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak: sneak::<S>,
+                    len: S::SIDE,
+                },
+                PhantomData,
+            )
         }
-        fn square(place: usize) -> PlaceIter {
-            fn sneak(index: usize) -> usize {
-                index + if index % 3 == 2 { 7 } else { 1 }
+        fn diagonal(place: usize) -> Self {
+            fn sneak<S: Size>(index: usize) -> usize {
+                index + S::SIDE + 1
+            }
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak: sneak::<S>,
+                    len: S::SIDE,
+                },
+                PhantomData,
+            )
+        }
+        fn anti_diagonal(place: usize) -> Self {
+            fn sneak<S: Size>(index: usize) -> usize {
+                index + S::SIDE - 1
+            }
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak: sneak::<S>,
+                    len: S::SIDE,
+                },
+                PhantomData,
+            )
+        }
+        fn slant(place: usize, direction: super::Slant) -> Self {
+            fn down_right<S: Size>(index: usize) -> usize {
+                index + S::SIDE + 1
+            }
+            fn down_left<S: Size>(index: usize) -> usize {
+                index + S::SIDE - 1
             }
-            PlaceIter {
-                place,
-                sneak,
-                len: 9,
+            // 最後のセルの次は盤面の外となるため、桁あふれを許容する。
+            fn up_right<S: Size>(index: usize) -> usize {
+                index.wrapping_sub(S::SIDE - 1)
             }
+            fn up_left<S: Size>(index: usize) -> usize {
+                index.wrapping_sub(S::SIDE + 1)
+            }
+            let (x, y) = (place % S::SIDE, place / S::SIDE);
+            let (sneak, width, height): (fn(usize) -> usize, _, _) = match direction {
+                super::Slant::DownRight => (down_right::<S>, S::SIDE - x, S::SIDE - y),
+                super::Slant::DownLeft => (down_left::<S>, x + 1, S::SIDE - y),
+                super::Slant::UpRight => (up_right::<S>, S::SIDE - x, y + 1),
+                super::Slant::UpLeft => (up_left::<S>, x + 1, y + 1),
+            };
+            PlaceIter(
+                Cursor::Sneak {
+                    place,
+                    sneak,
+                    len: width.min(height),
+                },
+                PhantomData,
+            )
+        }
+        fn region(cells: Rc<[usize]>) -> Self {
+            PlaceIter(Cursor::List(cells, 0), PhantomData)
         }
     }
 
-    impl Iterator for PlaceIter {
-        type Item = Place;
+    impl<S: Size> Iterator for PlaceIter<S> {
+        type Item = Place<S>;
         fn next(&mut self) -> Option<Self::Item> {
-            if 0 != self.len {
-                let res = Some(Place(self.place));
-                self.place = (self.sneak)(self.place);
-                self.len -= 1;
-                res
-            } else {
-                None
+            match &mut self.0 {
+                Cursor::Sneak { place, sneak, len } => {
+                    if 0 != *len {
+                        let res = Some(Place(*place, PhantomData));
+                        *place = sneak(*place);
+                        *len -= 1;
+                        res
+                    } else {
+                        None
+                    }
+                }
+                Cursor::List(cells, i) => {
+                    let res = cells.get(*i).map(|&place| Place(place, PhantomData));
+                    *i += 1;
+                    res
+                }
             }
         }
     }
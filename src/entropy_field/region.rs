@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn validate() {
+        let standard: Vec<usize> = (0..Standard::CELLS)
+            .map(|i| (i / 27) * 3 + (i % 9) / 3)
+            .collect();
+        RegionMap::<Standard>::new(&standard).unwrap();
+
+        let mut ids = standard.clone();
+        ids.pop();
+        assert_eq!(
+            RegionMap::<Standard>::new(&ids).unwrap_err(),
+            RegionMapError::Length(80)
+        );
+
+        let mut ids = standard.clone();
+        ids[0] = 9;
+        assert_eq!(
+            RegionMap::<Standard>::new(&ids).unwrap_err(),
+            RegionMapError::Id(9)
+        );
+
+        let mut ids = standard.clone();
+        ids[0] = 1;
+        assert_eq!(
+            RegionMap::<Standard>::new(&ids).unwrap_err(),
+            RegionMapError::Count(0, 8)
+        );
+
+        // 左上と右下のセルを入れ替えると、どちらの領域も分断される。
+        let mut ids = standard;
+        ids.swap(0, 80);
+        assert_eq!(
+            RegionMap::<Standard>::new(&ids).unwrap_err(),
+            RegionMapError::Disconnected(0)
+        );
+    }
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "aabb\naabb\nccdd\ncdcd"
+                .parse::<RegionMap<Size4>>()
+                .unwrap_err(),
+            RegionMapError::Disconnected(2)
+        );
+        let map: RegionMap<Size4> = "aaab\nacbb\ncccb\ndddd".parse().unwrap();
+        assert_eq!(map.region_of(&Place::new(3, 0).unwrap()), 1);
+        assert_eq!(map.region_of(&Place::new(1, 1).unwrap()), 2);
+        assert_eq!(map.region_of(&Place::new(3, 3).unwrap()), 3);
+    }
+}
+
+use super::place::*;
+use super::size::*;
+use std::rc::Rc;
+
+/// 不規則な形の領域(ジグソー数独)の割り当てを表します。
+/// 各セルについて、そのセルが属する領域の番号を保持します。
+#[derive(Debug, Clone)]
+pub struct RegionMap<S: Size = Standard> {
+    /// セルごとの領域の番号
+    ids: Vec<usize>,
+    /// 領域ごとに、属するセルのインデックスを昇順に並べたもの
+    regions: Vec<Rc<[usize]>>,
+    _size: std::marker::PhantomData<S>,
+}
+
+impl<S: Size> RegionMap<S> {
+    /// セルごとの領域の番号の表から新しいRegionMapを返します。
+    /// 表は先頭のセルから順に並べ、領域の番号は0以上、盤面の一辺のセル数未満とします。
+    /// 各領域が盤面の一辺と同じ数のセルを持ち、縦横に連結していない場合はエラーを返します。
+    pub fn new(ids: &[usize]) -> Result<Self, RegionMapError> {
+        if ids.len() != S::CELLS {
+            return Err(RegionMapError::Length(ids.len()));
+        }
+        let mut regions = vec![Vec::with_capacity(S::SIDE); S::SIDE];
+        for (i, &id) in ids.iter().enumerate() {
+            regions.get_mut(id).ok_or(RegionMapError::Id(id))?.push(i);
+        }
+        for (id, cells) in regions.iter().enumerate() {
+            if cells.len() != S::SIDE {
+                return Err(RegionMapError::Count(id, cells.len()));
+            }
+            // 最初のセルから縦横に辿れるセルを数える。
+            let mut visited = vec![false; S::CELLS];
+            let mut stack = vec![cells[0]];
+            visited[cells[0]] = true;
+            let mut count = 0;
+            while let Some(i) = stack.pop() {
+                count += 1;
+                let (x, y) = (i % S::SIDE, i / S::SIDE);
+                let neighbors = [
+                    (x > 0).then(|| i - 1),
+                    (x + 1 < S::SIDE).then(|| i + 1),
+                    (y > 0).then(|| i - S::SIDE),
+                    (y + 1 < S::SIDE).then(|| i + S::SIDE),
+                ];
+                for j in neighbors.into_iter().flatten() {
+                    if ids[j] == id && !visited[j] {
+                        visited[j] = true;
+                        stack.push(j);
+                    }
+                }
+            }
+            if count != S::SIDE {
+                return Err(RegionMapError::Disconnected(id));
+            }
+        }
+        Ok(RegionMap {
+            ids: ids.to_owned(),
+            regions: regions.into_iter().map(Into::into).collect(),
+            _size: std::marker::PhantomData,
+        })
+    }
+
+    /// 指定された位置のセルが属する領域の番号を返します。
+    pub fn region_of(&self, place: &Place<S>) -> usize {
+        self.ids[*place.raw()]
+    }
+
+    /// 指定された番号の領域に属するセルのインデックスを返します。
+    pub(crate) fn cells(&self, id: usize) -> &Rc<[usize]> {
+        &self.regions[id]
+    }
+}
+
+impl<S: Size> std::str::FromStr for RegionMap<S> {
+    type Err = RegionMapError;
+    /// 1行に盤面の一辺と同じ数の文字を並べた表から領域を読み込みます。
+    /// 同じ文字のセルが同じ領域となり、領域の番号は文字が最初に現れた順に割り当てられます。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars: Vec<char> = Vec::new();
+        let mut ids = Vec::with_capacity(S::CELLS);
+        for line in s.lines() {
+            let line: Vec<char> = line.chars().collect();
+            if line.len() != S::SIDE {
+                return Err(RegionMapError::Length(ids.len() + line.len()));
+            }
+            for c in line {
+                let id = match chars.iter().position(|&d| d == c) {
+                    Some(id) => id,
+                    None => {
+                        chars.push(c);
+                        chars.len() - 1
+                    }
+                };
+                ids.push(id);
+            }
+        }
+        RegionMap::new(&ids)
+    }
+}
+
+/// 領域の割り当てが不正な場合のエラーです。
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegionMapError {
+    /// 表の長さがセルの個数と一致しない。
+    Length(usize),
+    /// 領域の番号が範囲外である。
+    Id(usize),
+    /// 領域のセル数が盤面の一辺のセル数と一致しない。
+    Count(usize, usize),
+    /// 領域が縦横に連結していない。
+    Disconnected(usize),
+}
+
+impl std::fmt::Display for RegionMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use RegionMapError::*;
+        match self {
+            Length(len) => write!(f, "領域の表の長さが不正です: {len}"),
+            Id(id) => write!(f, "領域の番号が範囲外です: {id}"),
+            Count(id, count) => write!(f, "領域{id}のセル数が不正です: {count}"),
+            Disconnected(id) => write!(f, "領域{id}が連結していません。"),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/// 盤面の大きさを表すトレイトです。
+/// ボックスの幅と高さから、盤面の一辺のセル数やセルの個数が決まります。
+pub trait Size: Clone + Copy + std::fmt::Debug + PartialEq + Eq + 'static {
+    /// ボックスの横方向のセル数
+    const BOX_WIDTH: usize;
+    /// ボックスの縦方向のセル数
+    const BOX_HEIGHT: usize;
+    /// ボックスを持つかどうか
+    /// 持たない場合、ボックスの幅と高さは一辺のセル数を決めるためにのみ用いられます。
+    const HAS_BOXES: bool = true;
+    /// 盤面の一辺のセル数です。値の種類の数と等しくなります。
+    const SIDE: usize = Self::BOX_WIDTH * Self::BOX_HEIGHT;
+    /// セルの個数
+    const CELLS: usize = Self::SIDE * Self::SIDE;
+}
+
+/// ボックスの幅がWセル、高さがHセルの盤面の大きさです。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Boxed<const W: usize, const H: usize>;
+
+impl<const W: usize, const H: usize> Size for Boxed<W, H> {
+    const BOX_WIDTH: usize = W;
+    const BOX_HEIGHT: usize = H;
+}
+
+/// 2x2のボックスを持つ4x4の盤面
+pub type Size4 = Boxed<2, 2>;
+/// 横3x縦2のボックスを持つ6x6の盤面
+pub type Size6 = Boxed<3, 2>;
+/// 横4x縦2のボックスを持つ8x8の盤面
+pub type Size8 = Boxed<4, 2>;
+/// 3x3のボックスを持つ通常の9x9の盤面
+pub type Size9 = Boxed<3, 3>;
+/// 横4x縦3のボックスを持つ12x12の盤面
+pub type Size12 = Boxed<4, 3>;
+/// 4x4のボックスを持つ16x16の盤面
+pub type Size16 = Boxed<4, 4>;
+/// 5x5のボックスを持つ25x25の盤面
+pub type Size25 = Boxed<5, 5>;
+
+/// ボックスを持たないNxNのラテン方陣の盤面の大きさです。
+/// 行と列のみがブロックとなり、`Layout::new`の構成でもボックスは用いられません。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Latin<const N: usize>;
+
+impl<const N: usize> Size for Latin<N> {
+    const BOX_WIDTH: usize = N;
+    const BOX_HEIGHT: usize = 1;
+    const HAS_BOXES: bool = false;
+}
+
+/// 型引数を省略した場合に用いられる盤面の大きさです。
+pub type Standard = Size9;
@@ -1,6 +1,15 @@
 pub mod brute_force;
 pub mod entropy_field;
+pub mod multi_field;
+pub mod strategy;
+#[cfg(test)]
+mod testing;
 pub use brute_force::Attacker;
 pub use entropy_field::EntropyField;
+pub use entropy_field::constraint::{Constraint, Propagator};
 pub use entropy_field::entropy::{Entropy, Value};
+pub use entropy_field::layout::Layout;
 pub use entropy_field::place::Place;
+pub use entropy_field::size::{Boxed, Latin, Size, Standard};
+pub use multi_field::MultiField;
+pub use strategy::Solver;
@@ -1,18 +1,113 @@
+use entropy_field::constraint::{LittleKiller, ParityCells};
+use entropy_field::place::Slant;
 use number_place::*;
 
+/// 盤面の9行に続く追加の指定を読み込み、それに従ったEntropyFieldを返します。
+/// 各指定は見出しの行で始まります。
+/// - `regions`: 続く9行で不規則な領域を指定します。同じ文字のセルが同じ領域となります。
+/// - `diagonal`: 2本の対角線にも1から9が1つずつ入ります。
+/// - `windoku`: 4つのウィンドウの領域にも1から9が1つずつ入ります。
+/// - `parity`: 続く9行でセルの偶奇を指定します。`o`は奇数、`e`は偶数、`.`は指定なしです。
+/// - `littlekiller X Y 向き 合計`: 盤面の外の(X, Y)から斜めに向けたリトルキラーの手がかりです。
+///   座標は-1から9、向きは`dr`(右下)、`dl`(左下)、`ur`(右上)、`ul`(左上)のいずれかです。
+fn read_options(mut lines: impl Iterator<Item = String>) -> EntropyField {
+    let mut layout = Layout::new();
+    let mut parities = None;
+    let mut little_killers = Vec::new();
+    while let Some(header) = lines.next() {
+        let mut words = header.split_whitespace();
+        match words.next().unwrap_or_default() {
+            "" => continue,
+            "regions" => {
+                let table: Vec<String> = lines.by_ref().take(9).collect();
+                match table.join("\n").parse() {
+                    Ok(regions) => layout = layout.with_regions(regions),
+                    Err(error) => {
+                        eprintln!("{error}");
+                        panic!("入力形式が正しくありません。");
+                    }
+                }
+            }
+            "diagonal" => layout = layout.with_diagonals(),
+            "windoku" => layout = layout.with_windows(),
+            "parity" => {
+                let table: Vec<String> = lines.by_ref().take(9).collect();
+                match table.join("\n").parse::<ParityCells>() {
+                    Ok(cells) => parities = Some(cells),
+                    Err(_) => panic!("入力形式が正しくありません。"),
+                }
+            }
+            "littlekiller" => match read_little_killer(words) {
+                Some(clue) => little_killers.push(clue),
+                None => panic!("入力形式が正しくありません。"),
+            },
+            _ => panic!("入力形式が正しくありません。"),
+        }
+    }
+    let mut field = EntropyField::with_layout(layout);
+    if let Some(parities) = parities {
+        add_constraint(&mut field, parities);
+    }
+    for clue in little_killers {
+        add_constraint(&mut field, clue);
+    }
+    field
+}
+
+/// `X Y 向き 合計`の形式のリトルキラーの手がかりを読み込みます。
+fn read_little_killer<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<LittleKiller> {
+    let x: isize = words.next()?.parse().ok()?;
+    let y: isize = words.next()?.parse().ok()?;
+    let direction = match words.next()? {
+        "dr" => Slant::DownRight,
+        "dl" => Slant::DownLeft,
+        "ur" => Slant::UpRight,
+        "ul" => Slant::UpLeft,
+        _ => return None,
+    };
+    let sum = words.next()?.parse().ok()?;
+    // 手がかりの位置から1つ進んだセルが斜めの列の端となる。
+    let (dx, dy) = direction.offset();
+    let start = Place::new((x + dx).try_into().ok()?, (y + dy).try_into().ok()?)?;
+    LittleKiller::new(start, direction, sum)
+}
+
+/// 連鎖をEureka記法で返します。
+/// 各候補は`(値)r行c列`と1始まりの座標で表し、強いリンクを`=`、弱いリンクを`-`でつなぎます。
+fn eureka(chain: &[strategy::Link]) -> String {
+    let candidate =
+        |(place, value): &(Place, Value)| format!("({value})r{}c{}", place.y() + 1, place.x() + 1);
+    let mut notation = candidate(chain[0].from());
+    for link in chain {
+        notation.push(if link.is_strong() { '=' } else { '-' });
+        notation.push_str(&candidate(link.to()));
+    }
+    notation
+}
+
+/// 盤面にルールを追加します。ルール違反が検出された場合は終了します。
+fn add_constraint(field: &mut EntropyField, constraint: impl Constraint + 'static) {
+    if let Err(error) = field.add_constraint(constraint) {
+        eprintln!("{error}");
+        panic!("ルール違反が検出されました。");
+    }
+}
+
 fn main() {
+    let mut lines = std::io::stdin().lines().map(Result::unwrap);
+    let board: Vec<String> = lines.by_ref().take(9).collect();
+    if board.len() != 9 {
+        panic!("入力形式が正しくありません。");
+    }
+    let mut field = read_options(lines);
     // 入力した文字数
     let mut char_count = 0;
-    let mut field = EntropyField::new();
-    for y in 0..9 {
-        let mut line = String::new();
-        std::io::stdin().read_line(&mut line).unwrap();
+    for (y, line) in board.iter().enumerate() {
         let line = line.as_bytes();
-        if line.len() != 10 {
+        if line.len() != 9 {
             panic!("入力形式が正しくありません。");
         }
-        for x in 0..9 {
-            let c = line[x];
+        for (x, &c) in line.iter().enumerate() {
             char_count += 1;
             if !(b'1'..=b'9').contains(&c) {
                 continue;
@@ -33,6 +128,38 @@ fn main() {
     }
     println!("{field}");
 
+    // `--explain`が指定された場合は、総当たりの前に解法で解き進めて手順と難易度を表示する。
+    // `--unique`も指定された場合は、解が一意であることを前提とする解法も用いる。
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--explain") && field.len() > 1. {
+        println!();
+        println!("======STRATEGY======");
+        let solver = if args.iter().any(|arg| arg == "--unique") {
+            Solver::assuming_unique()
+        } else {
+            Solver::standard()
+        };
+        let solution = match solver.solve(field) {
+            Ok(solution) => solution,
+            Err(error) => {
+                eprintln!("{error}");
+                panic!("ルール違反が検出されました。");
+            }
+        };
+        for step in solution.steps() {
+            println!("{step}");
+            if !step.chain().is_empty() {
+                println!("  {}", eureka(step.chain()));
+            }
+        }
+        match solution.hardest() {
+            Some(step) => println!("RATING: {}", step.technique()),
+            None => println!("RATING: シングル"),
+        }
+        field = solution.field().clone();
+        println!("{field}");
+    }
+
     if field.len() > 1. {
         println!();
         println!("======BRUTE-FORCE======");
@@ -0,0 +1,308 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::solve_first;
+    #[test]
+    fn shared_cells() {
+        let mut field: MultiField = MultiField::samurai();
+        // 左上の盤面の右下のボックスは、中央の盤面の左上のボックスと共有される。
+        let place = GridPlace::new(0, Place::new(7, 7).unwrap());
+        field.insert(place, Value::FIVE).unwrap();
+        let center = field.grid(2).unwrap();
+        assert_eq!(
+            center.entropy_at(&Place::new(1, 1).unwrap()),
+            &Value::FIVE.into()
+        );
+        // 中央の盤面での伝播は、右上の盤面の共有されたボックスにも伝わる。
+        let top_right = field.grid(1).unwrap();
+        assert!(
+            !top_right
+                .entropy_at(&Place::new(1, 7).unwrap())
+                .is_possible(&Value::FIVE)
+        );
+        // 共有されていない右上の盤面のセルには伝わらない。
+        assert!(
+            top_right
+                .entropy_at(&Place::new(1, 0).unwrap())
+                .is_possible(&Value::FIVE)
+        );
+    }
+    #[test]
+    fn solve() {
+        // 左上、右上、中央、左下、右下の盤面の問題と解答で、共有されたボックスにも数字がある。
+        let givens = [
+            "546.3.1...2........3....7......5..8.8.....5..6..8....97...962..2....79..4..5..8..",
+            "......9..951..3...8..4..5......1..82....45...6.9....5.1...2...8.......76.9..7..1.",
+            "2.....1..9...1....8...5..9......9...7.........4..6.3......3.8.13...8.......4.5...",
+            ".3.......2.....3.....97....12......3.5.6.1...4......2..851........73....6.7..5...",
+            "8.1..9.37........8....4.2...5...2391.9..15.........8..98....12..73........43.....",
+        ];
+        let answers = [
+            "546739128927618453138245796374951682892364517615872349781496235253187964469523871",
+            "342758961951263847867491523534917682728645139619382754176524398285139476493876215",
+            "235894176964317285871652493623149758718523964549768312457936821396281547182475639",
+            "839216457271854396564973182128549763753621849496387521385162974942738615617495238",
+            "821569437547123968639847215456782391398415672712936854985674123173298546264351789",
+        ];
+        let mut field: MultiField = MultiField::samurai();
+        for (g, givens) in givens.into_iter().enumerate() {
+            for (i, c) in givens.bytes().enumerate() {
+                if c == b'.' {
+                    continue;
+                }
+                let value = Value::new((c - b'0').into()).unwrap();
+                let place = Place::new_from_raw(i).unwrap();
+                field.insert(GridPlace::new(g, place), value).unwrap();
+            }
+        }
+        let found = solve_first(field);
+        for (grid, answer) in found.grids.iter().zip(answers) {
+            for (i, c) in answer.bytes().enumerate() {
+                let value = Value::new((c - b'0').into()).unwrap();
+                let place = Place::new_from_raw(i).unwrap();
+                assert_eq!(grid.entropy_at(&place), &value.into());
+            }
+        }
+        for (g, (grid, (ox, oy))) in found.grids.iter().zip(found.offsets.iter()).enumerate() {
+            for i in 0..Standard::CELLS {
+                let place = Place::new_from_raw(i).unwrap();
+                for (h, other) in found.shared(g, &place) {
+                    let (px, py) = (ox + place.x(), oy + place.y());
+                    assert_eq!(
+                        other,
+                        Place::new(px - found.offsets[h].0, py - found.offsets[h].1).unwrap()
+                    );
+                    assert_eq!(grid.entropy_at(&place), found.grids[h].entropy_at(&other));
+                }
+            }
+        }
+        assert_eq!(found.len(), 1.);
+    }
+}
+
+use super::*;
+use brute_force::Searchable;
+use entropy_field::*;
+use size::*;
+use std::rc::Rc;
+
+/// 複数の盤面の一部のセルを共有させた、合体数独の盤面です。
+/// 共有されたセルへの適用は、そのセルを含む全ての盤面に伝播します。
+#[derive(Clone)]
+pub struct MultiField<S: Size = Standard> {
+    grids: Vec<EntropyField<S>>,
+    /// 各盤面の左上のセルの、全体での位置
+    offsets: Rc<Vec<(usize, usize)>>,
+}
+
+/// MultiFieldの中の1つの盤面上の位置を表します。
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct GridPlace<S: Size = Standard> {
+    /// 盤面の番号
+    pub grid: usize,
+    /// その盤面上の位置
+    pub place: Place<S>,
+}
+
+impl<S: Size> GridPlace<S> {
+    /// 新しいGridPlaceを返します。
+    pub fn new(grid: usize, place: Place<S>) -> Self {
+        GridPlace { grid, place }
+    }
+}
+
+impl<S: Size> std::fmt::Display for GridPlace<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}{}", self.grid, self.place)
+    }
+}
+
+/// MultiFieldの中の1つの盤面でルール違反が検出されたエラーです。
+#[derive(Debug)]
+pub struct MultiFieldError<S: Size = Standard> {
+    /// 違反が検出された盤面の番号
+    pub grid: usize,
+    pub error: RuleViolationError<S>,
+}
+
+impl<S: Size> std::fmt::Display for MultiFieldError<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}: {}", self.grid, self.error)
+    }
+}
+
+impl MultiField {
+    /// 5つの9x9の盤面からなるサムライナンプレ(合体5)の盤面を返します。
+    /// 盤面の番号は左上、右上、中央、左下、右下の順となり、
+    /// 中央の盤面の四隅のボックスを他の4つの盤面と共有します。
+    pub fn samurai() -> Self {
+        let grids = [(0, 0), (12, 0), (6, 6), (0, 12), (12, 12)]
+            .into_iter()
+            .map(|offset| (EntropyField::new(), offset))
+            .collect();
+        MultiField::new(grids).unwrap()
+    }
+}
+
+impl<S: Size> MultiField<S> {
+    /// 盤面と、その左上のセルの全体での位置の組から新しいMultiFieldを返します。
+    /// 全体で同じ位置となるセルが共有され、各盤面のエントロピーが重ねあわされます。
+    /// 重ねあわせた結果ルール違反が検出された場合はErr(MultiFieldError)を返します。
+    pub fn new(grids: Vec<(EntropyField<S>, (usize, usize))>) -> Result<Self, MultiFieldError<S>> {
+        let (grids, offsets) = grids.into_iter().unzip();
+        let mut field = MultiField {
+            grids,
+            offsets: Rc::new(offsets),
+        };
+        for g in 0..field.grids.len() {
+            for i in 0..S::CELLS {
+                let place = unsafe { Place::new_from_raw_unchecked(i) };
+                let entropy = field.grids[g].entropy_at(&place).to_owned();
+                if entropy.len() as usize != S::SIDE {
+                    field.insert(GridPlace::new(g, place), entropy)?;
+                }
+            }
+        }
+        Ok(field)
+    }
+
+    /// 盤面の個数を返します。
+    pub fn grid_count(&self) -> usize {
+        self.grids.len()
+    }
+
+    /// 指定された番号の盤面を返します。
+    pub fn grid(&self, grid: usize) -> Option<&EntropyField<S>> {
+        self.grids.get(grid)
+    }
+
+    /// 現在確認できたエントロピーの総量を返します。
+    /// 共有されたセルはそれを含む盤面の数だけ数えます。
+    pub fn len(&self) -> f64 {
+        self.grids.iter().map(EntropyField::len).product()
+    }
+
+    /// 指定された盤面のセルと共有されている、他の盤面のセルを返します。
+    fn shared(&self, grid: usize, place: &Place<S>) -> Vec<(usize, Place<S>)> {
+        let (ox, oy) = self.offsets[grid];
+        let (x, y) = (ox + place.x(), oy + place.y());
+        let mut shared = Vec::new();
+        for (h, &(hx, hy)) in self.offsets.iter().enumerate() {
+            if h == grid || x < hx || y < hy {
+                continue;
+            }
+            if let Some(other) = Place::new(x - hx, y - hy) {
+                shared.push((h, other));
+            }
+        }
+        shared
+    }
+
+    /// 指定された位置のセルにエントロピーを適用します。
+    /// 共有されたセルのエントロピーが縮小した場合は、共有している全ての盤面に伝播させます。
+    /// ルール違反が検出された場合はErr(MultiFieldError)を返します。
+    /// その場合、EntropyField::insertと同様に、それまでの伝播で縮小した盤面はそのまま残ります。
+    pub fn insert(
+        &mut self,
+        place: GridPlace<S>,
+        into_entropy: impl Into<Entropy<S>>,
+    ) -> Result<(), MultiFieldError<S>> {
+        let mut remaining_sets = vec![(place, into_entropy.into())];
+        while let Some((GridPlace { grid, place }, entropy)) = remaining_sets.pop() {
+            // 共有されたセルについてのみ、適用前のエントロピーを控える。
+            let watched: Vec<_> = (0..S::CELLS)
+                .map(|i| unsafe { Place::new_from_raw_unchecked(i) })
+                .filter_map(|place| {
+                    let shared = self.shared(grid, &place);
+                    if shared.is_empty() {
+                        return None;
+                    }
+                    let before = self.grids[grid].entropy_at(&place).to_owned();
+                    Some((place, before, shared))
+                })
+                .collect();
+            self.grids[grid]
+                .insert(place, entropy)
+                .map_err(|error| MultiFieldError { grid, error })?;
+            // 縮小したセルのうち、共有されているものを他の盤面に伝える。
+            for (place, before, shared) in watched {
+                let entropy = self.grids[grid].entropy_at(&place);
+                if &before == entropy {
+                    continue;
+                }
+                for (h, other) in shared {
+                    if self.grids[h].entropy_at(&other) != entropy {
+                        remaining_sets.push((GridPlace::new(h, other), entropy.to_owned()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> std::fmt::Display for MultiField<S> {
+    /// 全ての盤面を重ねた全体を表示します。
+    /// どの盤面にも含まれない位置は空白となります。
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = S::SIDE.to_string().len();
+        let columns = self
+            .offsets
+            .iter()
+            .map(|(x, _)| x + S::SIDE)
+            .max()
+            .unwrap_or(0);
+        let rows = self
+            .offsets
+            .iter()
+            .map(|(_, y)| y + S::SIDE)
+            .max()
+            .unwrap_or(0);
+        for y in 0..rows {
+            if y != 0 {
+                writeln!(f)?;
+            }
+            for x in 0..columns {
+                let cell = self
+                    .offsets
+                    .iter()
+                    .zip(&self.grids)
+                    .find_map(|(&(ox, oy), grid)| {
+                        let place = Place::new(x.checked_sub(ox)?, y.checked_sub(oy)?)?;
+                        Some(grid.entropy_at(&place).to_owned())
+                    });
+                match cell {
+                    Some(entropy) => match entropy.clone().try_into() {
+                        Ok(value) => {
+                            let value: Value<S> = value;
+                            write!(f, " {:>width$} ", value.to_string())?
+                        }
+                        Err(_) => write!(f, "[{:>width$}]", entropy.len())?,
+                    },
+                    None => write!(f, " {:>width$} ", "")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: Size> Searchable for MultiField<S> {
+    type Size = S;
+    type Place = GridPlace<S>;
+    type Error = MultiFieldError<S>;
+    fn len(&self) -> f64 {
+        MultiField::len(self)
+    }
+    fn first_entropy(&self) -> Option<(GridPlace<S>, Entropy<S>)> {
+        for (g, grid) in self.grids.iter().enumerate() {
+            if let Some((place, entropy)) = grid.first_entropy() {
+                return Some((GridPlace::new(g, place), entropy));
+            }
+        }
+        None
+    }
+    fn insert(&mut self, place: GridPlace<S>, value: Value<S>) -> Result<(), MultiFieldError<S>> {
+        MultiField::insert(self, place, value)
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn almost_locked_sets() {
+        let mut field = EntropyField::new();
+        restrict(
+            &mut field,
+            &[((0, 0), 0b110), ((4, 0), 0b1010), ((4, 4), 0b1100)],
+        );
+        let sets = AlmostLockedSet::find_all(&field);
+        assert!(
+            sets.iter()
+                .any(|set| set.cells() == [Place::new(0, 0).unwrap()])
+        );
+        assert!(sets.iter().any(|set| {
+            set.cells() == [Place::new(4, 0).unwrap(), Place::new(4, 4).unwrap()]
+                && set.candidates() == Entropy::try_from(0b1110).unwrap()
+        }));
+        let step = AlsXz.find(&field).unwrap();
+        assert_eq!(
+            step.eliminations(),
+            &[(Place::new(0, 4).unwrap(), Value::TWO)]
+        );
+
+        // 1行目と左上のボックスの交差に4つの値があり、行側の{1, 2}とボックス側の{3, 4}で埋まる。
+        let mut field = EntropyField::new();
+        restrict(
+            &mut field,
+            &[
+                ((0, 0), 0b11110),
+                ((1, 0), 0b11110),
+                ((5, 0), 0b110),
+                ((0, 1), 0b11000),
+            ],
+        );
+        let step = SueDeCoq.find(&field).unwrap();
+        assert_eq!(step.eliminations().len(), 24);
+        assert!(
+            step.eliminations()
+                .contains(&(Place::new(8, 0).unwrap(), Value::ONE))
+        );
+        assert!(
+            step.eliminations()
+                .contains(&(Place::new(2, 2).unwrap(), Value::FOUR))
+        );
+    }
+}
+
+use super::*;
+
+/// セルの一覧を文章に含める形式で返します。
+fn cells_string<S: Size>(cells: &[Place<S>]) -> String {
+    cells.iter().map(ToString::to_string).collect()
+}
+
+/// ほぼロックされたセルの組(ALS)です。
+/// 同じブロック内のn個の収束していないセルで、候補を合わせてn+1種類の値を持つものを表します。
+/// いずれか1つの値を否定すると、残りのn種類の値がn個のセルに入ります。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlmostLockedSet<S: Size = Standard> {
+    /// 位置の順に並べたセル
+    cells: Vec<Place<S>>,
+    /// セルの候補を合わせたビット列
+    candidates: BITS,
+}
+
+impl<S: Size> AlmostLockedSet<S> {
+    /// 盤面の各ブロックに含まれるALSを、セルの少ない順に重複なく全て返します。
+    pub fn find_all(field: &EntropyField<S>) -> Vec<Self> {
+        let mut sets: Vec<Self> = Vec::new();
+        for unit in units(field) {
+            let unsolved: Vec<Place<S>> = unit
+                .into_iter()
+                .filter(|cell| field.entropy_at(cell).len() > 1)
+                .collect();
+            for size in 1..unsolved.len() {
+                for cells in combinations(&unsolved, size) {
+                    let candidates = cells
+                        .iter()
+                        .fold(0, |union, cell| union | bits(field, cell));
+                    let set = AlmostLockedSet { cells, candidates };
+                    if candidates.count_ones() as usize == size + 1 && !sets.contains(&set) {
+                        sets.push(set);
+                    }
+                }
+            }
+        }
+        sets.sort_by_key(|set| set.cells.len());
+        sets
+    }
+    /// ALSに含まれるセルを位置の順に返します。
+    pub fn cells(&self) -> &[Place<S>] {
+        &self.cells
+    }
+    /// ALSのセルの候補を合わせたエントロピーを返します。
+    pub fn candidates(&self) -> Entropy<S> {
+        Entropy::try_from(self.candidates).unwrap()
+    }
+    /// ALSのうち、指定された値の入り得るセルを返します。
+    fn cells_with(&self, field: &EntropyField<S>, value: &Value<S>) -> Vec<Place<S>> {
+        self.cells
+            .iter()
+            .filter(|cell| field.entropy_at(cell).is_possible(value))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<S: Size> std::fmt::Display for AlmostLockedSet<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", cells_string(&self.cells), self.candidates())
+    }
+}
+
+/// ALS-XZの検出です。
+/// セルを共有しない2つのALSがともに持つ値Xについて、一方のXの入り得るセルが全て
+/// もう一方のXの入り得るセルと同じブロックに含まれる場合、Xはどちらか一方にしか入りません。
+/// Xの入らない方のALSは残りの値で埋まるので、ともに持つ別の値Zはどちらかに入ります。
+/// 両方のALSのZの入り得るセル全てと同じブロックに含まれるセルからZを否定します。
+#[derive(Debug, Clone, Default)]
+pub struct AlsXz;
+
+impl std::fmt::Display for AlsXz {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ALS-XZ")
+    }
+}
+
+impl<S: Size> Strategy<S> for AlsXz {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        let sets = AlmostLockedSet::find_all(field);
+        for (i, a) in sets.iter().enumerate() {
+            for b in &sets[i + 1..] {
+                let common = a.candidates & b.candidates;
+                if common.count_ones() < 2 || a.cells.iter().any(|cell| b.cells.contains(cell)) {
+                    continue;
+                }
+                for x in values::<S>(common) {
+                    let (a_x, b_x) = (a.cells_with(field, &x), b.cells_with(field, &x));
+                    if !a_x.iter().all(|p| b_x.iter().all(|q| sees(field, p, q))) {
+                        continue;
+                    }
+                    for z in values::<S>(common).into_iter().filter(|z| z != &x) {
+                        let mut targets = a.cells_with(field, &z);
+                        targets.extend(b.cells_with(field, &z));
+                        let eliminations: Vec<_> = seen_by_all(field, &targets, &z)
+                            .into_iter()
+                            .filter(|(place, _)| {
+                                !a.cells.contains(place) && !b.cells.contains(place)
+                            })
+                            .collect();
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+                        let description = format!(
+                            "ALS {a}とALS {b}は{x}を同時に持たないので、{z}はどちらかに入る"
+                        );
+                        return Some(Step::new("ALS-XZ", description, eliminations));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Sue de Coqの検出です。
+/// 行または列と領域の交差にある2つ以上のセルの候補が、セルの数より2つ以上多い値を持つ場合に、
+/// 行または列の残りのセルと、領域の残りのセルからそれぞれセルの組を選びます。
+/// 2つの組の候補が重ならず、全てのセルの候補を合わせた値の数がセルの数と等しければ、
+/// 各値はちょうど1つのセルに入ります。行または列の組の値と、交差の値のうち領域の組にないものを
+/// 行または列の他のセルから否定し、領域についても同様に否定します。
+#[derive(Debug, Clone, Default)]
+pub struct SueDeCoq;
+
+impl std::fmt::Display for SueDeCoq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sue de Coq")
+    }
+}
+
+/// 組として選ぶセルの数の上限
+const MAX_GROUP: usize = 3;
+
+impl<S: Size> Strategy<S> for SueDeCoq {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        if !field.layout().boxes() {
+            return None;
+        }
+        let mut regions: Vec<Vec<Place<S>>> = Vec::new();
+        for i in 0..S::CELLS {
+            let place = unsafe { Place::new_from_raw_unchecked(i) };
+            let region: Vec<Place<S>> = field.dependencies(&place).region().into_iter().collect();
+            if !regions.contains(&region) {
+                regions.push(region);
+            }
+        }
+        for region in &regions {
+            let mut lines = Vec::new();
+            for cell in region {
+                let dependencies = field.dependencies(cell);
+                for line in [dependencies.x_line(), dependencies.y_line()] {
+                    let line: Vec<Place<S>> = line.into_iter().collect();
+                    if !lines.contains(&line) {
+                        lines.push(line);
+                    }
+                }
+            }
+            for line in &lines {
+                let unsolved = |cells: &[Place<S>], inside: &[Place<S>], within: bool| {
+                    cells
+                        .iter()
+                        .filter(|cell| {
+                            inside.contains(cell) == within && field.entropy_at(cell).len() > 1
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>()
+                };
+                // 交差のセルと、行または列の残りのセルと、領域の残りのセル
+                let cross = unsolved(region, line, true);
+                let line_rest = unsolved(line, region, false);
+                let region_rest = unsolved(region, line, false);
+                for size in 2..=cross.len() {
+                    for core in combinations(&cross, size) {
+                        let core_bits =
+                            core.iter().fold(0, |union, cell| union | bits(field, cell));
+                        if (core_bits.count_ones() as usize) < size + 2 {
+                            continue;
+                        }
+                        if let Some(step) = sue_de_coq(
+                            field,
+                            line,
+                            region,
+                            &core,
+                            core_bits,
+                            &line_rest,
+                            &region_rest,
+                        ) {
+                            return Some(step);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 交差のセルの組coreについて、行または列と領域からセルの組を選んでSue de Coqを探します。
+fn sue_de_coq<S: Size>(
+    field: &EntropyField<S>,
+    line: &[Place<S>],
+    region: &[Place<S>],
+    core: &[Place<S>],
+    core_bits: BITS,
+    line_rest: &[Place<S>],
+    region_rest: &[Place<S>],
+) -> Option<Step<S>> {
+    let groups = |cells: &[Place<S>]| -> Vec<(Vec<Place<S>>, BITS)> {
+        (1..=MAX_GROUP.min(cells.len()))
+            .flat_map(|size| combinations(cells, size))
+            .map(|group| {
+                let bits = group
+                    .iter()
+                    .fold(0, |union, cell| union | bits(field, cell));
+                (group, bits)
+            })
+            // 交差と値を共有しない組は、否定に寄与しない。
+            .filter(|(_, bits)| bits & core_bits != 0)
+            .collect()
+    };
+    let (line_groups, region_groups) = (groups(line_rest), groups(region_rest));
+    for (line_group, line_bits) in &line_groups {
+        for (region_group, region_bits) in &region_groups {
+            let union = core_bits | line_bits | region_bits;
+            let count = core.len() + line_group.len() + region_group.len();
+            if line_bits & region_bits != 0 || union.count_ones() as usize != count {
+                continue;
+            }
+            let used: Vec<&Place<S>> = core.iter().chain(line_group).chain(region_group).collect();
+            let mut eliminations = Vec::new();
+            let targets = [
+                (line, line_bits | core_bits & !region_bits),
+                (region, region_bits | core_bits & !line_bits),
+            ];
+            for i in 0..S::CELLS {
+                let place = unsafe { Place::new_from_raw_unchecked(i) };
+                if used.contains(&&place) || field.entropy_at(&place).len() < 2 {
+                    continue;
+                }
+                let removed = targets
+                    .iter()
+                    .filter(|(cells, _)| cells.contains(&place))
+                    .fold(0, |removed, (_, bits)| removed | bits);
+                for value in values(bits(field, &place) & removed) {
+                    eliminations.push((place.clone(), value));
+                }
+            }
+            if eliminations.is_empty() {
+                continue;
+            }
+            let description = format!(
+                "交差{}{}の値は、行または列の{}{}と領域の{}{}とともに1つずつ入る",
+                cells_string(core),
+                Entropy::<S>::try_from(core_bits).unwrap(),
+                cells_string(line_group),
+                Entropy::<S>::try_from(*line_bits).unwrap(),
+                cells_string(region_group),
+                Entropy::<S>::try_from(*region_bits).unwrap()
+            );
+            return Some(Step::new("Sue de Coq", description, eliminations));
+        }
+    }
+    None
+}
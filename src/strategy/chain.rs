@@ -0,0 +1,295 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn chains() {
+        // 2行目と6行目で1が3列目と7列目にしか入らない。
+        let mut field: EntropyField = EntropyField::new();
+        let without_one = Entropy::try_from(0b1111111100).unwrap();
+        for y in [1, 5] {
+            for x in [0, 1, 3, 4, 5, 7, 8] {
+                field
+                    .insert(Place::new(x, y).unwrap(), without_one.clone())
+                    .unwrap();
+            }
+        }
+        assert!(Chaining::SimpleColoring.find(&field).is_none());
+        let step = Chaining::XChain.find(&field).unwrap();
+        assert_eq!(step.technique(), "X-Chain");
+        assert_eq!(step.eliminations().len(), 7);
+        let links: Vec<_> = step
+            .chain()
+            .iter()
+            .map(|link| (link.to().0.clone(), link.is_strong()))
+            .collect();
+        assert_eq!(step.chain()[0].from().0, Place::new(2, 1).unwrap());
+        assert_eq!(
+            links,
+            [
+                (Place::new(6, 1).unwrap(), true),
+                (Place::new(6, 5).unwrap(), false),
+                (Place::new(2, 5).unwrap(), true)
+            ]
+        );
+
+        let mut field: EntropyField = EntropyField::new();
+        for ((x, y), bits) in [((0, 0), 0b110), ((4, 0), 0b1100), ((4, 8), 0b1010)] {
+            field
+                .insert(Place::new(x, y).unwrap(), Entropy::try_from(bits).unwrap())
+                .unwrap();
+        }
+        let step = Chaining::XyChain.find(&field).unwrap();
+        assert_eq!(step.chain().len(), 5);
+        assert_eq!(
+            step.eliminations(),
+            &[(Place::new(0, 8).unwrap(), Value::ONE)]
+        );
+    }
+}
+
+use super::*;
+
+/// 候補(セルと値の組)の間のリンクです。
+/// 強いリンクは一方が偽ならばもう一方が真となり、
+/// 弱いリンクは一方が真ならばもう一方が偽となる関係を表します。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link<S: Size = Standard> {
+    from: (Place<S>, Value<S>),
+    to: (Place<S>, Value<S>),
+    strong: bool,
+}
+
+impl<S: Size> Link<S> {
+    /// リンクの始点の候補を返します。
+    pub fn from(&self) -> &(Place<S>, Value<S>) {
+        &self.from
+    }
+    /// リンクの終点の候補を返します。
+    pub fn to(&self) -> &(Place<S>, Value<S>) {
+        &self.to
+    }
+    /// 強いリンクかどうかを返します。
+    pub fn is_strong(&self) -> bool {
+        self.strong
+    }
+}
+
+/// 強いリンクと弱いリンクを交互につないだ連鎖(AIC)の検出です。
+/// 強いリンクで始まり強いリンクで終わる3つ以上のリンクの連鎖では、始点と終点の候補の少なくとも一方が真となるので、
+/// 両方の候補と矛盾する候補を否定します。
+/// 用いるリンクの種類によって、易しい順に4つの解法に分かれます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chaining {
+    /// 1つの値について、2つしか入り得ないブロックの組(共役対)のみをつないだ連鎖
+    SimpleColoring,
+    /// 1つの値について、強いリンクを共役対とし、弱いリンクを同じブロックのセルとした連鎖
+    XChain,
+    /// 強いリンクを候補が2つのセルの中とし、弱いリンクを同じ値の同じブロックのセルとした連鎖
+    XyChain,
+    /// 全ての種類のリンクを用いる連鎖
+    Aic,
+}
+
+impl std::fmt::Display for Chaining {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Chaining::SimpleColoring => write!(f, "Simple Coloring"),
+            Chaining::XChain => write!(f, "X-Chain"),
+            Chaining::XyChain => write!(f, "XY-Chain"),
+            Chaining::Aic => write!(f, "AIC"),
+        }
+    }
+}
+
+/// 候補を表す番号です。セルの番号と値から一意に決まります。
+type Node = usize;
+
+/// 候補の番号を返します。
+fn node<S: Size>(place: &Place<S>, value: &Value<S>) -> Node {
+    place.raw() * S::SIDE + BITS::from(value.clone()) as usize - 1
+}
+
+/// 番号が表す候補を返します。
+fn candidate<S: Size>(node: Node) -> (Place<S>, Value<S>) {
+    let place = unsafe { Place::new_from_raw_unchecked(node / S::SIDE) };
+    let value = unsafe { Value::new_unchecked((node % S::SIDE + 1) as BITS) };
+    (place, value)
+}
+
+/// 収束していないセルの候補の間のリンクを、候補ごとに強いリンクと弱いリンクに分けて持ちます。
+/// 強いリンクは弱いリンクとしても用いることができるため、弱いリンクの一覧にも含めます。
+struct Graph {
+    strong: Vec<Vec<Node>>,
+    weak: Vec<Vec<Node>>,
+}
+
+impl Chaining {
+    /// 盤面から連鎖に用いるリンクを集めます。
+    fn graph<S: Size>(&self, field: &EntropyField<S>) -> Graph {
+        let mut graph = Graph {
+            strong: vec![Vec::new(); S::CELLS * S::SIDE],
+            weak: vec![Vec::new(); S::CELLS * S::SIDE],
+        };
+        let cell_links = matches!(self, Chaining::XyChain | Chaining::Aic);
+        let unit_links = !matches!(self, Chaining::XyChain);
+        for i in 0..S::CELLS {
+            let place = unsafe { Place::new_from_raw_unchecked(i) };
+            let entropy = field.entropy_at(&place);
+            if entropy.len() < 2 {
+                continue;
+            }
+            for value in entropy.clone() {
+                let from = node(&place, &value);
+                // 同じセルの他の値
+                for other in entropy.clone().into_iter().filter(|other| other != &value) {
+                    let to = node(&place, &other);
+                    if cell_links && entropy.len() == 2 {
+                        graph.strong[from].push(to);
+                    }
+                    if matches!(self, Chaining::Aic) {
+                        graph.weak[from].push(to);
+                    }
+                }
+                // 同じブロックの同じ値
+                for block in field.dependencies(&place) {
+                    let cells: Vec<Place<S>> = block
+                        .into_iter()
+                        .filter(|cell| {
+                            let entropy = field.entropy_at(cell);
+                            entropy.len() > 1 && entropy.is_possible(&value)
+                        })
+                        .collect();
+                    for cell in cells.iter().filter(|&cell| cell != &place) {
+                        let to = node(cell, &value);
+                        if cells.len() == 2 {
+                            if unit_links {
+                                graph.strong[from].push(to);
+                            }
+                            graph.weak[from].push(to);
+                        } else if !matches!(self, Chaining::SimpleColoring) {
+                            graph.weak[from].push(to);
+                        }
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+/// 連鎖の始点と終点の少なくとも一方が真となる場合に否定できる候補を返します。
+fn eliminations<S: Size>(
+    field: &EntropyField<S>,
+    (start, start_value): &(Place<S>, Value<S>),
+    (end, end_value): &(Place<S>, Value<S>),
+) -> Vec<(Place<S>, Value<S>)> {
+    if start_value == end_value {
+        return seen_by_all(field, &[start.clone(), end.clone()], start_value);
+    }
+    if start == end {
+        // 同じセルの2つの値のいずれかが入るので、他の値は入らない。
+        return field
+            .entropy_at(start)
+            .to_owned()
+            .into_iter()
+            .filter(|value| value != start_value && value != end_value)
+            .map(|value| (start.clone(), value))
+            .collect();
+    }
+    if !sees(field, start, end) {
+        return Vec::new();
+    }
+    // 互いに同じブロックにあるので、一方の値が入らなければもう一方の値が入る。
+    let mut eliminations = Vec::new();
+    if field.entropy_at(start).is_possible(end_value) {
+        eliminations.push((start.clone(), end_value.clone()));
+    }
+    if field.entropy_at(end).is_possible(start_value) {
+        eliminations.push((end.clone(), start_value.clone()));
+    }
+    eliminations
+}
+
+impl<S: Size> Strategy<S> for Chaining {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        let graph = self.graph(field);
+        for start in 0..S::CELLS * S::SIDE {
+            if graph.strong[start].is_empty() {
+                continue;
+            }
+            // 始点から幅優先で探索し、短い連鎖を優先する。
+            // 探索した候補と、その親の位置と、そこへ至るリンクが強いかどうかを並べる。
+            let mut visited: Vec<(Node, usize, bool)> = vec![(start, 0, false)];
+            let mut reached = vec![[false; 2]; S::CELLS * S::SIDE];
+            let mut cursor = 0;
+            while cursor < visited.len() {
+                let (current, _, arrived_strong) = visited[cursor];
+                // 始点からは強いリンクで出発し、以降は交互にリンクをたどる。
+                let strong = cursor == 0 || !arrived_strong;
+                let links = if strong {
+                    &graph.strong[current]
+                } else {
+                    &graph.weak[current]
+                };
+                for &next in links {
+                    if reached[next][strong as usize] || on_path(&visited, cursor, next) {
+                        continue;
+                    }
+                    reached[next][strong as usize] = true;
+                    visited.push((next, cursor, strong));
+                    // 1つの強いリンクのみでは連鎖とせず、3つ以上のリンクからなるものを探す。
+                    if !strong || cursor == 0 {
+                        continue;
+                    }
+                    let eliminations = eliminations(field, &candidate(start), &candidate(next));
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    let chain = chain(&visited, visited.len() - 1);
+                    let (start, end) = (candidate::<S>(start), candidate::<S>(next));
+                    let description = format!(
+                        "{}の{}と{}の{}の少なくとも一方が入る({}リンク)",
+                        start.0,
+                        start.1,
+                        end.0,
+                        end.1,
+                        chain.len()
+                    );
+                    let step = Step::new(self.to_string(), description, eliminations);
+                    return Some(step.with_chain(chain));
+                }
+                cursor += 1;
+            }
+        }
+        None
+    }
+}
+
+/// 探索した位置indexまでの経路に候補nodeが含まれるかどうかを返します。
+fn on_path(visited: &[(Node, usize, bool)], mut index: usize, node: Node) -> bool {
+    loop {
+        if visited[index].0 == node {
+            return true;
+        }
+        if index == 0 {
+            return false;
+        }
+        index = visited[index].1;
+    }
+}
+
+/// 探索した位置indexまでの経路を、始点から順のリンクの列として返します。
+fn chain<S: Size>(visited: &[(Node, usize, bool)], mut index: usize) -> Vec<Link<S>> {
+    let mut chain = Vec::new();
+    while index != 0 {
+        let (to, parent, strong) = visited[index];
+        chain.push(Link {
+            from: candidate(visited[parent].0),
+            to: candidate(to),
+            strong,
+        });
+        index = parent;
+    }
+    chain.reverse();
+    chain
+}
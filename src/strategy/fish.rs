@@ -0,0 +1,298 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    fn without_one(field: &mut EntropyField, cells: &[(usize, usize)]) {
+        let mut entropy = Entropy::new();
+        entropy.disable(&Value::ONE).unwrap();
+        for &(x, y) in cells {
+            field
+                .insert(Place::new(x, y).unwrap(), entropy.clone())
+                .unwrap();
+        }
+    }
+    /// 各行で1の入り得る列を指定し、それ以外のセルを返します。
+    fn outside(rows: &[(usize, &[usize])]) -> Vec<(usize, usize)> {
+        rows.iter()
+            .flat_map(|&(y, xs)| (0..9).filter(|x| !xs.contains(x)).map(move |x| (x, y)))
+            .collect()
+    }
+    #[test]
+    fn x_wing() {
+        let mut field = EntropyField::new();
+        let cells: Vec<_> = [1, 5]
+            .into_iter()
+            .flat_map(|y| [0, 1, 3, 4, 5, 7, 8].map(|x| (x, y)))
+            .collect();
+        without_one(&mut field, &cells);
+        assert!(Fish::finned(2).unwrap().find(&field).is_none());
+        let step = Fish::new(2).unwrap().find(&field).unwrap();
+        assert_eq!(step.technique(), "X-Wing");
+        assert_eq!(
+            step.description(),
+            "1が1行目, 5行目では2列目, 6列目にのみ入る"
+        );
+        assert_eq!(step.eliminations().len(), 14);
+        assert!(
+            step.eliminations()
+                .contains(&(Place::new(6, 8).unwrap(), Value::ONE))
+        );
+
+        // 6行目の(7, 5)をヒレとすると、そのボックス内の6列目から1を否定できる。
+        let mut field = EntropyField::new();
+        let cells: Vec<_> = [0, 1, 3, 4, 5, 7, 8]
+            .map(|x| (x, 1))
+            .into_iter()
+            .chain([0, 1, 3, 4, 5, 8].map(|x| (x, 5)))
+            .collect();
+        without_one(&mut field, &cells);
+        assert!(Fish::new(2).unwrap().find(&field).is_none());
+        let step = Fish::finned(2).unwrap().find(&field).unwrap();
+        assert_eq!(step.technique(), "Finned X-Wing");
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(6, 3).unwrap(), Value::ONE),
+                (Place::new(6, 4).unwrap(), Value::ONE)
+            ]
+        );
+    }
+    #[test]
+    fn larger_fish() {
+        let mut field = EntropyField::new();
+        without_one(
+            &mut field,
+            &outside(&[(0, &[0, 4]), (4, &[4, 8]), (8, &[0, 8])]),
+        );
+        assert!(Fish::new(2).unwrap().find(&field).is_none());
+        let step = Fish::new(3).unwrap().find(&field).unwrap();
+        assert_eq!(step.technique(), "Swordfish");
+        assert_eq!(step.eliminations().len(), 18);
+
+        let mut field = EntropyField::new();
+        without_one(
+            &mut field,
+            &outside(&[(0, &[0, 3]), (3, &[3, 6]), (6, &[6, 8]), (8, &[8, 0])]),
+        );
+        assert!(Fish::new(3).unwrap().find(&field).is_none());
+        let step = Fish::new(4).unwrap().find(&field).unwrap();
+        assert_eq!(step.technique(), "Jellyfish");
+        assert_eq!(step.eliminations().len(), 20);
+    }
+    #[test]
+    fn sashimi() {
+        // (6, 5)に1が入らなくとも、ヒレ(7, 5)と(8, 5)を除けば2行目と共にX-Wingとなる。
+        let mut field = EntropyField::new();
+        without_one(&mut field, &outside(&[(1, &[2, 6]), (5, &[2, 7, 8])]));
+        let step = Fish::finned(2).unwrap().find(&field).unwrap();
+        assert_eq!(step.technique(), "Sashimi X-Wing");
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(6, 3).unwrap(), Value::ONE),
+                (Place::new(6, 4).unwrap(), Value::ONE)
+            ]
+        );
+        // ヒレが2つのボックスに分かれる場合は否定できない。
+        let mut field = EntropyField::new();
+        without_one(&mut field, &outside(&[(1, &[2, 6]), (5, &[0, 2, 6, 7])]));
+        assert!(Fish::finned(2).unwrap().find(&field).is_none());
+    }
+}
+
+use super::*;
+use entropy_field::constraint::Line;
+
+/// 魚(X-Wing、Swordfish、Jellyfish)の検出です。
+/// ある値について、n本の行でその値の入り得るセルが合わせてn本の列に収まる場合、
+/// それらの列の他の行のセルからその値を否定します。行と列を入れ替えた場合も同様です。
+/// ヒレ付きの魚では、列に収まらないセル(ヒレ)が1つの領域にまとまっている場合に、
+/// ヒレと同じ領域にある列のセルからのみ値を否定します。
+#[derive(Debug, Clone)]
+pub struct Fish {
+    /// 基準とする行または列の本数
+    size: usize,
+    /// ヒレ付きの魚を探すかどうか
+    finned: bool,
+}
+
+impl Fish {
+    /// 指定された本数の基本的な魚を探すFishを返します。
+    /// 本数が2から4でない場合はNoneが返ります。
+    pub fn new(size: usize) -> Option<Self> {
+        (2..=4).contains(&size).then_some(Fish {
+            size,
+            finned: false,
+        })
+    }
+    /// 指定された本数のヒレ付きの魚を探すFishを返します。
+    /// ヒレを除くと基本的な魚にならない場合(Sashimi)も含みます。
+    /// 本数が2から4でない場合はNoneが返ります。
+    pub fn finned(size: usize) -> Option<Self> {
+        (2..=4)
+            .contains(&size)
+            .then_some(Fish { size, finned: true })
+    }
+    /// 基準とする行または列の本数を返します。
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    /// ヒレ付きの魚を探すかどうかを返します。
+    pub fn is_finned(&self) -> bool {
+        self.finned
+    }
+    /// 本数に応じた魚の名前を返します。
+    fn name(&self) -> &'static str {
+        match self.size {
+            2 => "X-Wing",
+            3 => "Swordfish",
+            _ => "Jellyfish",
+        }
+    }
+}
+
+impl std::fmt::Display for Fish {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.finned {
+            write!(f, "Finned ")?;
+        }
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 基準とする線のi本目と、被覆する線のj本目が交わるセルの位置を返します。
+fn cross<S: Size>(by_row: bool, i: usize, j: usize) -> Place<S> {
+    let (x, y) = if by_row { (j, i) } else { (i, j) };
+    unsafe { Place::new_unchecked(x, y) }
+}
+
+/// 基準とする線の向きでi本目の線を返します。
+fn line(by_row: bool, i: usize) -> Line {
+    if by_row {
+        Line::Row(i)
+    } else {
+        Line::Column(i)
+    }
+}
+
+/// 線の一覧を文章に含める形式で返します。
+fn lines(by_row: bool, indices: &[usize]) -> String {
+    let lines: Vec<String> = indices
+        .iter()
+        .map(|&i| line(by_row, i).to_string())
+        .collect();
+    lines.join(", ")
+}
+
+impl Fish {
+    /// 基準とする線の組について魚を調べます。
+    /// masksは基準とする向きの各線で、値の入り得る被覆の線の位置を表すビット列です。
+    fn try_base<S: Size>(
+        &self,
+        field: &EntropyField<S>,
+        value: &Value<S>,
+        by_row: bool,
+        masks: &[BITS],
+        base: &[usize],
+    ) -> Option<Step<S>> {
+        let union = base.iter().fold(0, |union, &i| union | masks[i]);
+        let others: Vec<usize> = (0..S::SIDE).filter(|i| !base.contains(i)).collect();
+        if union.count_ones() as usize == self.size {
+            if self.finned {
+                return None;
+            }
+            let covers: Vec<usize> = indices(union).collect();
+            let eliminations: Vec<_> = others
+                .iter()
+                .flat_map(|&i| indices(masks[i] & union).map(move |j| (i, j)))
+                .map(|(i, j)| (cross(by_row, i, j), value.clone()))
+                .collect();
+            if eliminations.is_empty() {
+                return None;
+            }
+            let description = format!(
+                "{value}が{}では{}にのみ入る",
+                lines(by_row, base),
+                lines(!by_row, &covers)
+            );
+            return Some(Step::new(self.name(), description, eliminations));
+        }
+        // ヒレは1つの領域に収まるので、被覆の外の線はボックスの一辺の本数を超えない。
+        // 不規則な領域は形が定まらないため、盤面の一辺の本数までとする。
+        let limit = match field.regions() {
+            Some(_) => S::SIDE,
+            None => S::BOX_WIDTH.max(S::BOX_HEIGHT),
+        };
+        let spread = (union.count_ones() as usize).saturating_sub(self.size);
+        if !self.finned || !field.layout().boxes() || spread > limit {
+            return None;
+        }
+        let candidates: Vec<usize> = indices(union).collect();
+        for covers in combinations(&candidates, self.size) {
+            let cover = covers.iter().fold(0, |cover, &j| cover | 1 << j);
+            // 基準の各線は被覆の線に少なくとも1つの候補を持つ。
+            if base.iter().any(|&i| masks[i] & cover == 0) {
+                continue;
+            }
+            let fins: Vec<Place<S>> = base
+                .iter()
+                .flat_map(|&i| indices(masks[i] & !cover).map(move |j| cross(by_row, i, j)))
+                .collect();
+            let region = region_of(field, &fins[0]);
+            if fins.iter().any(|fin| region_of(field, fin) != region) {
+                continue;
+            }
+            let eliminations: Vec<_> = others
+                .iter()
+                .flat_map(|&i| indices(masks[i] & cover).map(move |j| cross(by_row, i, j)))
+                .filter(|place| region_of(field, place) == region)
+                .map(|place| (place, value.clone()))
+                .collect();
+            if eliminations.is_empty() {
+                continue;
+            }
+            // ヒレを除くと候補が1つしか残らない線があればSashimiとなる。
+            let kind = if base.iter().any(|&i| (masks[i] & cover).count_ones() < 2) {
+                "Sashimi"
+            } else {
+                "Finned"
+            };
+            let fins: Vec<String> = fins.iter().map(ToString::to_string).collect();
+            let description = format!(
+                "{value}が{}ではヒレ{}を除き{}にのみ入る",
+                lines(by_row, base),
+                fins.join(", "),
+                lines(!by_row, &covers)
+            );
+            let technique = format!("{kind} {}", self.name());
+            return Some(Step::new(technique, description, eliminations));
+        }
+        None
+    }
+}
+
+impl<S: Size> Strategy<S> for Fish {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        for value in Entropy::<S>::new() {
+            for by_row in [true, false] {
+                // 収束していないセルのみを数えるため、値の確定した線は空となる。
+                let masks: Vec<BITS> = (0..S::SIDE)
+                    .map(|i| {
+                        (0..S::SIDE)
+                            .filter(|&j| {
+                                let entropy = field.entropy_at(&cross(by_row, i, j));
+                                entropy.len() > 1 && entropy.is_possible(&value)
+                            })
+                            .fold(0, |mask, j| mask | 1 << j)
+                    })
+                    .collect();
+                let bases: Vec<usize> = (0..S::SIDE).filter(|&i| masks[i] != 0).collect();
+                for base in combinations(&bases, self.size) {
+                    if let Some(step) = self.try_base(field, &value, by_row, &masks, &base) {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,421 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn rating() {
+        let mut field = EntropyField::new();
+        let without_one = {
+            let mut entropy = Entropy::new();
+            entropy.disable(&Value::ONE).unwrap();
+            entropy
+        };
+        // 2行目と6行目で1が3列目と7列目にしか入らないX-Wingを作る。
+        for y in [1, 5] {
+            for x in [0, 1, 3, 4, 5, 7, 8] {
+                field
+                    .insert(Place::new(x, y).unwrap(), without_one.clone())
+                    .unwrap();
+            }
+        }
+        let solution = Solver::standard().solve(field).unwrap();
+        assert!(!solution.is_solved());
+        let hardest = solution.hardest().unwrap();
+        assert_eq!(hardest.technique(), "X-Wing");
+        assert_eq!(solution.steps().count(), 1);
+        assert!(
+            !solution
+                .field()
+                .entropy_at(&Place::new(2, 0).unwrap())
+                .is_possible(&Value::ONE)
+        );
+
+        let solution: Solution = Solver::standard().solve(EntropyField::new()).unwrap();
+        assert!(solution.hardest().is_none());
+    }
+    #[test]
+    fn contradiction() {
+        // 1行目の3つのセルに1と2しか入らない、矛盾した盤面を作る。
+        let mut field: EntropyField = EntropyField::new();
+        restrict(
+            &mut field,
+            &[((0, 0), 0b110), ((1, 0), 0b110), ((2, 0), 0b110)],
+        );
+        // 伝播による解法が矛盾を検出した場合も、候補を否定せずにErrを返す。
+        let solver = Solver::new().with_strategy(NakedSubsets::new(2).unwrap());
+        assert!(solver.solve(field.clone()).is_err());
+        assert!(Solver::standard().solve(field).is_err());
+    }
+}
+
+pub mod als;
+pub mod chain;
+pub mod fish;
+pub mod uniqueness;
+pub mod wing;
+
+pub use als::{AlmostLockedSet, AlsXz, SueDeCoq};
+pub use chain::{Chaining, Link};
+pub use fish::Fish;
+pub use uniqueness::{Bug, UniqueRectangle};
+pub use wing::{WWing, XyWing, XyzWing};
+
+use super::*;
+use entropy::BITS;
+use entropy_field::constraint::{HiddenSubsets, LockedCandidates, NakedSubsets};
+use entropy_field::*;
+use size::*;
+use std::rc::Rc;
+
+/// 人が用いる解法の1つを表すトレイトです。
+/// 総当たりの探索とは異なり、適用できた理由を説明できる手順を返します。
+pub trait Strategy<S: Size = Standard>: std::fmt::Debug + std::fmt::Display {
+    /// 盤面でこの解法を適用できる箇所を探し、見つかった場合はその手順を返します。
+    /// 返される手順は、少なくとも1つの候補を否定します。
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>>;
+    /// findと同様に手順を探しますが、探す途中で盤面のルール違反が検出された場合は
+    /// Err(RuleViolationError)を返します。
+    /// 既定ではfindの結果をそのまま返し、ルール違反を検出しません。
+    fn try_find(&self, field: &EntropyField<S>) -> Result<Option<Step<S>>, RuleViolationError<S>> {
+        Ok(self.find(field))
+    }
+}
+
+/// 解法を1回適用する手順です。
+#[derive(Debug, Clone)]
+pub struct Step<S: Size = Standard> {
+    /// 用いた解法の名前
+    technique: String,
+    /// 解法を適用できた理由の説明
+    description: String,
+    /// 否定される候補
+    eliminations: Vec<(Place<S>, Value<S>)>,
+    /// 連鎖による解法で用いたリンクの列
+    chain: Vec<Link<S>>,
+}
+
+impl<S: Size> Step<S> {
+    /// 解法の名前と説明、否定される候補から新しいStepを返します。
+    pub fn new(
+        technique: impl Into<String>,
+        description: impl Into<String>,
+        eliminations: Vec<(Place<S>, Value<S>)>,
+    ) -> Self {
+        Step {
+            technique: technique.into(),
+            description: description.into(),
+            eliminations,
+            chain: Vec::new(),
+        }
+    }
+    /// 連鎖による解法で用いたリンクの列を設定したStepを返します。
+    pub fn with_chain(self, chain: Vec<Link<S>>) -> Self {
+        Step { chain, ..self }
+    }
+    /// 用いた解法の名前を返します。
+    pub fn technique(&self) -> &str {
+        &self.technique
+    }
+    /// 解法を適用できた理由の説明を返します。
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+    /// 否定される候補の位置と値を返します。
+    pub fn eliminations(&self) -> &[(Place<S>, Value<S>)] {
+        &self.eliminations
+    }
+    /// 連鎖による解法で用いたリンクを始点から順に返します。
+    /// 連鎖を用いない解法では空となります。
+    pub fn chain(&self) -> &[Link<S>] {
+        &self.chain
+    }
+}
+
+impl<S: Size> std::fmt::Display for Step<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} =>", self.technique, self.description)?;
+        for (place, value) in &self.eliminations {
+            write!(f, " {place}≠{value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 登録された解法を易しい順に適用して盤面を解き進める構造体です。
+/// どの解法が必要になったかによって、パズルの難易度を評価できます。
+/// 単一の候補(シングル)は盤面の伝播で処理されるため、解法としては扱いません。
+#[derive(Debug, Clone)]
+pub struct Solver<S: Size = Standard> {
+    /// 易しい順に並べた解法
+    strategies: Vec<Rc<dyn Strategy<S>>>,
+}
+
+impl<S: Size> Default for Solver<S> {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+impl<S: Size> Solver<S> {
+    /// 解法を持たない新しいSolverを返します。
+    pub fn new() -> Self {
+        Solver {
+            strategies: Vec::new(),
+        }
+    }
+    /// 実装されている解法のうち、解が一意であることを前提としないものを易しい順に全て持つ
+    /// Solverを返します。
+    pub fn standard() -> Self {
+        Solver::build(false)
+    }
+    /// 実装されている解法を易しい順に全て持つSolverを返します。
+    /// ユニークレクタングルなど、解が一意であることを前提とする解法も含むため、
+    /// 解が一意であると分かっているパズルにのみ用います。
+    pub fn assuming_unique() -> Self {
+        Solver::build(true)
+    }
+    /// 解法を易しい順に並べたSolverを返します。
+    /// uniqueがtrueの場合は、解が一意であることを前提とする解法も含めます。
+    fn build(unique: bool) -> Self {
+        let mut solver = Solver::new().with_strategy(LockedCandidates);
+        for size in 2..=4 {
+            solver = solver
+                .with_strategy(NakedSubsets::new(size).unwrap())
+                .with_strategy(HiddenSubsets::new(size).unwrap());
+        }
+        for size in 2..=4 {
+            solver = solver.with_strategy(Fish::new(size).unwrap());
+        }
+        solver = solver
+            .with_strategy(Chaining::SimpleColoring)
+            .with_strategy(XyWing)
+            .with_strategy(XyzWing)
+            .with_strategy(WWing);
+        if unique {
+            for kind in 1..=4 {
+                solver = solver.with_strategy(UniqueRectangle::new(kind).unwrap());
+            }
+            solver = solver.with_strategy(Bug);
+        }
+        for size in 2..=4 {
+            solver = solver.with_strategy(Fish::finned(size).unwrap());
+        }
+        solver
+            .with_strategy(Chaining::XChain)
+            .with_strategy(Chaining::XyChain)
+            .with_strategy(Chaining::Aic)
+            .with_strategy(SueDeCoq)
+            .with_strategy(AlsXz)
+    }
+    /// 既存の解法より難しい解法として、指定された解法を追加したSolverを返します。
+    pub fn with_strategy(mut self, strategy: impl Strategy<S> + 'static) -> Self {
+        self.strategies.push(Rc::new(strategy));
+        self
+    }
+    /// 易しい順に並べた解法を返します。
+    pub fn strategies(&self) -> impl Iterator<Item = &dyn Strategy<S>> {
+        self.strategies.iter().map(|strategy| strategy.as_ref())
+    }
+    /// 盤面に解法を繰り返し適用し、どの解法も適用できなくなった時点の結果を返します。
+    /// 手順ごとに最も易しい解法から順に探すため、難しい解法は必要な場合にのみ用いられます。
+    /// 否定した候補によってルール違反が検出された場合はErr(RuleViolationError)を返します。
+    pub fn solve(&self, mut field: EntropyField<S>) -> Result<Solution<S>, RuleViolationError<S>> {
+        let mut steps = Vec::new();
+        'solve: while field.len() > 1. {
+            for (level, strategy) in self.strategies.iter().enumerate() {
+                if let Some(step) = strategy.try_find(&field)? {
+                    for (place, value) in step.eliminations() {
+                        let mut entropy = Entropy::new();
+                        entropy.disable(value).unwrap();
+                        field.insert(place.clone(), entropy)?;
+                    }
+                    steps.push((level, step));
+                    continue 'solve;
+                }
+            }
+            break;
+        }
+        Ok(Solution { field, steps })
+    }
+}
+
+/// Solverで解き進めた結果です。
+#[derive(Clone)]
+pub struct Solution<S: Size = Standard> {
+    /// 解き進めた後の盤面
+    field: EntropyField<S>,
+    /// 適用した順に並べた、解法の難しさの順位と手順の組
+    steps: Vec<(usize, Step<S>)>,
+}
+
+impl<S: Size> Solution<S> {
+    /// 解き進めた後の盤面を返します。
+    pub fn field(&self) -> &EntropyField<S> {
+        &self.field
+    }
+    /// 解法のみで全てのセルが収束したかどうかを返します。
+    pub fn is_solved(&self) -> bool {
+        self.field.len() == 1.
+    }
+    /// 適用した手順を順に返します。
+    pub fn steps(&self) -> impl Iterator<Item = &Step<S>> {
+        self.steps.iter().map(|(_, step)| step)
+    }
+    /// 適用した手順のうち、最も難しい解法による最初の手順を返します。
+    /// シングルのみで解き進められた場合はNoneが返ります。
+    pub fn hardest(&self) -> Option<&Step<S>> {
+        let level = self.steps.iter().map(|(level, _)| *level).max()?;
+        self.steps
+            .iter()
+            .find(|(l, _)| *l == level)
+            .map(|(_, step)| step)
+    }
+}
+
+/// ルールによる伝播を解法として用います。
+/// 盤面の複製にルールを追加し、否定された候補を手順として返します。
+/// ルールの追加でルール違反が検出された場合はErr(RuleViolationError)を返します。
+fn propagated<S: Size>(
+    field: &EntropyField<S>,
+    constraint: impl Constraint<S> + 'static,
+) -> Result<Option<Step<S>>, RuleViolationError<S>> {
+    let technique = constraint.to_string();
+    let mut next = field.clone();
+    next.add_constraint(constraint)?;
+    let mut eliminations = Vec::new();
+    for i in 0..S::CELLS {
+        let place = unsafe { Place::new_from_raw_unchecked(i) };
+        let before = field.entropy_at(&place).to_owned();
+        let after = next.entropy_at(&place);
+        for value in before.into_iter().filter(|value| !after.is_possible(value)) {
+            eliminations.push((place.clone(), value));
+        }
+    }
+    if eliminations.is_empty() {
+        return Ok(None);
+    }
+    let description = format!("伝播により{}個の候補を否定", eliminations.len());
+    Ok(Some(Step::new(technique, description, eliminations)))
+}
+
+impl<S: Size> Strategy<S> for LockedCandidates {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        self.try_find(field).ok().flatten()
+    }
+    fn try_find(&self, field: &EntropyField<S>) -> Result<Option<Step<S>>, RuleViolationError<S>> {
+        propagated(field, self.clone())
+    }
+}
+
+impl<S: Size> Strategy<S> for NakedSubsets {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        self.try_find(field).ok().flatten()
+    }
+    fn try_find(&self, field: &EntropyField<S>) -> Result<Option<Step<S>>, RuleViolationError<S>> {
+        propagated(field, self.clone())
+    }
+}
+
+impl<S: Size> Strategy<S> for HiddenSubsets {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        self.try_find(field).ok().flatten()
+    }
+    fn try_find(&self, field: &EntropyField<S>) -> Result<Option<Step<S>>, RuleViolationError<S>> {
+        propagated(field, self.clone())
+    }
+}
+
+/// itemsからn個を選ぶ組み合わせを全て返します。
+fn combinations<T: Clone>(items: &[T], n: usize) -> Vec<Vec<T>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut found = Vec::new();
+    for i in 0..items.len() {
+        for mut rest in combinations(&items[i + 1..], n - 1) {
+            rest.insert(0, items[i].clone());
+            found.push(rest);
+        }
+    }
+    found
+}
+
+/// ビット列で1となっている桁を順に返します。
+fn indices(bits: BITS) -> impl Iterator<Item = usize> {
+    (0..BITS::BITS as usize).filter(move |i| bits & 1 << i != 0)
+}
+
+/// セルの候補をビット列で返します。
+fn bits<S: Size>(field: &EntropyField<S>, place: &Place<S>) -> BITS {
+    field.entropy_at(place).to_owned().into()
+}
+
+/// ビット列が表す値を返します。
+fn values<S: Size>(bits: BITS) -> Vec<Value<S>> {
+    indices(bits)
+        .map(|i| Value::new(i as BITS).unwrap())
+        .collect()
+}
+
+/// テスト用に、指定されたセルの候補をビット列の値に絞ります。
+#[cfg(test)]
+fn restrict<S: Size>(field: &mut EntropyField<S>, cells: &[((usize, usize), BITS)]) {
+    for &((x, y), bits) in cells {
+        field
+            .insert(Place::new(x, y).unwrap(), Entropy::try_from(bits).unwrap())
+            .unwrap();
+    }
+}
+
+/// 指定された位置が属する領域を、その領域の最初のセルで表して返します。
+fn region_of<S: Size>(field: &EntropyField<S>, place: &Place<S>) -> Place<S> {
+    field
+        .dependencies(place)
+        .region()
+        .into_iter()
+        .next()
+        .unwrap()
+}
+
+/// 2つの位置が異なり、同じブロックに含まれるかどうかを返します。
+fn sees<S: Size>(field: &EntropyField<S>, a: &Place<S>, b: &Place<S>) -> bool {
+    a != b
+        && field
+            .dependencies(a)
+            .into_iter()
+            .any(|block| block.into_iter().any(|place| &place == b))
+}
+
+/// 指定された全てのセルと同じブロックに含まれる、収束していないセルのうち、
+/// 値の入り得るものを否定する候補として返します。
+fn seen_by_all<S: Size>(
+    field: &EntropyField<S>,
+    cells: &[Place<S>],
+    value: &Value<S>,
+) -> Vec<(Place<S>, Value<S>)> {
+    (0..S::CELLS)
+        .map(|i| unsafe { Place::new_from_raw_unchecked(i) })
+        .filter(|place| {
+            let entropy = field.entropy_at(place);
+            entropy.len() > 1
+                && entropy.is_possible(value)
+                && cells.iter().all(|cell| sees(field, cell, place))
+        })
+        .map(|place| (place, value.clone()))
+        .collect()
+}
+
+/// 盤面の構成に含まれる全てのブロックを、重複なくセルの一覧として返します。
+fn units<S: Size>(field: &EntropyField<S>) -> Vec<Vec<Place<S>>> {
+    let mut units: Vec<Vec<Place<S>>> = Vec::new();
+    for i in 0..S::CELLS {
+        let place = unsafe { Place::new_from_raw_unchecked(i) };
+        for block in field.dependencies(&place) {
+            let mut cells: Vec<Place<S>> = block.into_iter().collect();
+            cells.sort_by_key(|cell| *cell.raw());
+            if !units.contains(&cells) {
+                units.push(cells);
+            }
+        }
+    }
+    units
+}
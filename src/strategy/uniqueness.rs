@@ -0,0 +1,397 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    use entropy_field::constraint::{AntiKnight, LockedCandidates};
+    #[test]
+    fn deadly_patterns() {
+        let mut field: EntropyField = EntropyField::new();
+        restrict(
+            &mut field,
+            &[
+                ((0, 0), 0b110),
+                ((3, 0), 0b110),
+                ((0, 1), 0b110),
+                ((3, 1), 0b1110),
+            ],
+        );
+        let step = UniqueRectangle::new(1).unwrap().find(&field).unwrap();
+        assert_eq!(step.technique(), "Unique Rectangle Type 1");
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(3, 1).unwrap(), Value::ONE),
+                (Place::new(3, 1).unwrap(), Value::TWO)
+            ]
+        );
+        // 対角線のある盤面では、値を入れ替えた解が成り立つとは限らない。
+        let mut field: EntropyField = EntropyField::with_layout(Layout::new().with_diagonals());
+        restrict(
+            &mut field,
+            &[
+                ((0, 0), 0b110),
+                ((3, 0), 0b110),
+                ((0, 1), 0b110),
+                ((3, 1), 0b1110),
+            ],
+        );
+        assert!(UniqueRectangle::new(1).unwrap().find(&field).is_none());
+        assert!(Bug.find(&field).is_none());
+        // 追加のルールがある盤面も同様だが、基本ルールから導ける否定のみのルールは妨げない。
+        let mut field: EntropyField = EntropyField::new();
+        field.add_constraint(AntiKnight).unwrap();
+        let mut locked: EntropyField = EntropyField::new();
+        locked.add_constraint(LockedCandidates).unwrap();
+        for field in [&mut field, &mut locked] {
+            restrict(
+                field,
+                &[
+                    ((0, 0), 0b110),
+                    ((3, 0), 0b110),
+                    ((0, 1), 0b110),
+                    ((3, 1), 0b1110),
+                ],
+            );
+        }
+        assert!(UniqueRectangle::new(1).unwrap().find(&field).is_none());
+        assert!(UniqueRectangle::new(1).unwrap().find(&locked).is_some());
+
+        // 2行目では1が(0, 1)と(3, 1)にしか入らないので、2は入らない。
+        let mut field: EntropyField = EntropyField::new();
+        restrict(
+            &mut field,
+            &[
+                ((0, 0), 0b110),
+                ((3, 0), 0b110),
+                ((0, 1), 0b1110),
+                ((3, 1), 0b1110),
+            ],
+        );
+        let step = UniqueRectangle::new(2).unwrap().find(&field).unwrap();
+        assert_eq!(step.eliminations().len(), 7);
+        assert!(UniqueRectangle::new(4).unwrap().find(&field).is_none());
+        let cells: Vec<_> = [1, 2, 4, 5, 6, 7, 8]
+            .map(|x| ((x, 1), 0b1111111100))
+            .to_vec();
+        restrict(&mut field, &cells);
+        let step = UniqueRectangle::new(4).unwrap().find(&field).unwrap();
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(3, 1).unwrap(), Value::TWO),
+                (Place::new(0, 1).unwrap(), Value::TWO)
+            ]
+        );
+
+        // (0, 0)以外の全てのセルの候補が2つで、各ブロックに各値が2つずつ入り得る。
+        let mut field = EntropyField::<Latin<3>>::new();
+        let rows = [
+            [0b1110, 0b1100, 0b1010],
+            [0b1100, 0b1010, 0b0110],
+            [0b1010, 0b0110, 0b1100],
+        ];
+        for (y, row) in rows.into_iter().enumerate() {
+            let cells: Vec<_> = row
+                .into_iter()
+                .enumerate()
+                .map(|(x, bits)| ((x, y), bits))
+                .collect();
+            restrict(&mut field, &cells);
+        }
+        let step = Bug.find(&field).unwrap();
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(0, 0).unwrap(), Value::new(1).unwrap()),
+                (Place::new(0, 0).unwrap(), Value::new(2).unwrap())
+            ]
+        );
+    }
+}
+
+use super::*;
+
+/// 否定される候補の一覧です。
+type Eliminations<S> = Vec<(Place<S>, Value<S>)>;
+
+/// 盤面が数独の基本ルールと同じ解のみを許すルール(`Constraint::is_standard`)のみからなるかどうかを返します。
+/// 対角線やウィンドウ、その他のルールがあると、値を入れ替えた解が成り立つとは限りません。
+fn is_plain<S: Size>(field: &EntropyField<S>) -> bool {
+    let layout = field.layout();
+    !layout.diagonals()
+        && !layout.windows()
+        && field
+            .constraints()
+            .all(|constraint| constraint.is_standard())
+}
+
+/// ユニークレクタングルの検出です。
+/// 2行2列の交点にあり2つの領域にまたがる4つのセルが、全て同じ2つの値{a, b}のみを候補とすると、
+/// aとbを入れ替えた解も成り立つため、解が一意であれば4つのセルがそうなることはありません。
+/// この前提から、4つのセルの余分な候補の扱いによって4つの型に分かれます。
+/// - 1型: 3つのセルが{a, b}の場合、残りのセルからaとbを否定します。
+/// - 2型: 同じ行か列の2つのセル(床)が{a, b}で、残りの2つ(屋根)が同じ1つの余分な値cを持つ場合、
+///   屋根のどちらかにcが入るので、両方の屋根と同じブロックに含まれるセルからcを否定します。
+/// - 3型: 屋根の余分な値をまとめて1つのセルとみなし、屋根が共有するブロックで
+///   ネイキッドサブセットを作る場合、そのブロックの他のセルからそれらの値を否定します。
+/// - 4型: 屋根が共有するブロックでaが屋根にしか入らない場合、屋根からbを否定します。
+///
+/// 複数の解を持つパズルでは誤った否定を行うため、解が一意であると分かっている場合のみ用います。
+#[derive(Debug, Clone)]
+pub struct UniqueRectangle {
+    kind: usize,
+}
+
+impl UniqueRectangle {
+    /// 指定された型のユニークレクタングルを探すUniqueRectangleを返します。
+    /// 型が1から4でない場合はNoneが返ります。
+    pub fn new(kind: usize) -> Option<Self> {
+        (1..=4).contains(&kind).then_some(UniqueRectangle { kind })
+    }
+    /// 探す型を返します。
+    pub fn kind(&self) -> usize {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for UniqueRectangle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unique Rectangle Type {}", self.kind)
+    }
+}
+
+impl UniqueRectangle {
+    /// 4つのセルと2つの値{a, b}について、この型の否定と説明を返します。
+    /// cellsは長方形を一周する順に並べ、隣り合うセルは同じ行か列に含まれます。
+    fn check<S: Size>(
+        &self,
+        field: &EntropyField<S>,
+        cells: &[Place<S>; 4],
+        pair: BITS,
+    ) -> Option<(Eliminations<S>, String)> {
+        let exact: Vec<bool> = cells.iter().map(|cell| bits(field, cell) == pair).collect();
+        if self.kind == 1 {
+            if exact.iter().filter(|&&exact| exact).count() != 3 {
+                return None;
+            }
+            let corner = &cells[exact.iter().position(|&exact| !exact)?];
+            let eliminations = values(pair)
+                .into_iter()
+                .map(|value| (corner.clone(), value))
+                .collect();
+            return Some((eliminations, format!("{corner}以外は2択")));
+        }
+        // 床は長方形の隣り合う2つのセルで、屋根は残りの2つのセルとなる。
+        for i in 0..4 {
+            let floor = [&cells[i], &cells[(i + 1) % 4]];
+            let roof = [&cells[(i + 2) % 4], &cells[(i + 3) % 4]];
+            if !exact[i] || !exact[(i + 1) % 4] || exact[(i + 2) % 4] || exact[(i + 3) % 4] {
+                continue;
+            }
+            let roof_bits = [bits(field, roof[0]), bits(field, roof[1])];
+            let extra = (roof_bits[0] | roof_bits[1]) & !pair;
+            let description = format!("床{}と{}、屋根{}と{}", floor[0], floor[1], roof[0], roof[1]);
+            let roof_cells = [roof[0].clone(), roof[1].clone()];
+            let eliminations: Vec<_> = match self.kind {
+                2 => {
+                    if roof_bits[0] != roof_bits[1] || extra.count_ones() != 1 {
+                        continue;
+                    }
+                    seen_by_all(field, &roof_cells, &values(extra)[0])
+                }
+                3 => shared_units(field, &roof_cells)
+                    .into_iter()
+                    .find_map(|unit| pseudo_subset(field, &unit, &roof_cells, extra))
+                    .unwrap_or_default(),
+                _ => shared_units(field, &roof_cells)
+                    .into_iter()
+                    .find_map(|unit| {
+                        // aが屋根にしか入らなければ、屋根のどちらかがaとなりbは入らない。
+                        values::<S>(pair).into_iter().find_map(|value| {
+                            let positions: Vec<&Place<S>> = unit
+                                .iter()
+                                .filter(|cell| field.entropy_at(cell).is_possible(&value))
+                                .collect();
+                            (positions.len() == 2
+                                && roof_cells.iter().all(|r| positions.contains(&r)))
+                            .then(|| {
+                                let other = values(pair & !(1 << BITS::from(value)));
+                                roof_cells
+                                    .iter()
+                                    .map(|cell| (cell.clone(), other[0].clone()))
+                                    .collect()
+                            })
+                        })
+                    })
+                    .unwrap_or_default(),
+            };
+            if !eliminations.is_empty() {
+                return Some((eliminations, description));
+            }
+        }
+        None
+    }
+}
+
+/// 指定された全てのセルを含むブロックを返します。
+fn shared_units<S: Size>(field: &EntropyField<S>, cells: &[Place<S>]) -> Vec<Vec<Place<S>>> {
+    units(field)
+        .into_iter()
+        .filter(|unit| cells.iter().all(|cell| unit.contains(cell)))
+        .collect()
+}
+
+/// 屋根の余分な値extraを1つのセルとみなし、ブロック内の他のセルとともに
+/// ネイキッドサブセットを作る場合、そのブロックの残りのセルから否定できる候補を返します。
+fn pseudo_subset<S: Size>(
+    field: &EntropyField<S>,
+    unit: &[Place<S>],
+    roof: &[Place<S>],
+    extra: BITS,
+) -> Option<Eliminations<S>> {
+    let others: Vec<Place<S>> = unit
+        .iter()
+        .filter(|cell| !roof.contains(cell) && field.entropy_at(cell).len() > 1)
+        .cloned()
+        .collect();
+    for size in 1..others.len() {
+        for subset in combinations(&others, size) {
+            let union = subset
+                .iter()
+                .fold(extra, |union, cell| union | bits(field, cell));
+            if union.count_ones() as usize != size + 1 {
+                continue;
+            }
+            let eliminations: Vec<_> = others
+                .iter()
+                .filter(|cell| !subset.contains(cell))
+                .flat_map(|cell| {
+                    values::<S>(bits(field, cell) & union)
+                        .into_iter()
+                        .map(move |value| (cell.clone(), value))
+                })
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(eliminations);
+            }
+        }
+    }
+    None
+}
+
+impl<S: Size> Strategy<S> for UniqueRectangle {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        if !field.layout().boxes() || !is_plain(field) {
+            return None;
+        }
+        let pairs: Vec<(usize, usize)> = (0..S::SIDE)
+            .flat_map(|i| (i + 1..S::SIDE).map(move |j| (i, j)))
+            .collect();
+        for &(y1, y2) in &pairs {
+            for &(x1, x2) in &pairs {
+                let cells = [(x1, y1), (x2, y1), (x2, y2), (x1, y2)]
+                    .map(|(x, y)| unsafe { Place::new_unchecked(x, y) });
+                // 2つの領域がそれぞれ同じ行か列の2つのセルを含む場合のみ、値を入れ替えられる。
+                let regions = cells.clone().map(|cell| region_of(field, &cell));
+                let split = |i: usize| {
+                    regions[i] == regions[i + 1]
+                        && regions[(i + 2) % 4] == regions[(i + 3) % 4]
+                        && regions[i] != regions[(i + 2) % 4]
+                };
+                if !(split(0) || split(1))
+                    || cells.iter().any(|cell| field.entropy_at(cell).len() < 2)
+                {
+                    continue;
+                }
+                let common = cells
+                    .iter()
+                    .fold(BITS::MAX, |common, cell| common & bits(field, cell));
+                for pair in combinations(&indices(common).collect::<Vec<_>>(), 2) {
+                    let pair = 1 << pair[0] | 1 << pair[1];
+                    if let Some((eliminations, description)) = self.check(field, &cells, pair) {
+                        let values: Vec<String> =
+                            values::<S>(pair).iter().map(ToString::to_string).collect();
+                        let description = format!(
+                            "{}が{{{}}}のみとなると解が一意でない: {description}",
+                            cells
+                                .iter()
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(""),
+                            values.join(", ")
+                        );
+                        return Some(Step::new(self.to_string(), description, eliminations));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// BUG+1(Bivalue Universal Grave)の検出です。
+/// 収束していないセルが1つを除いて全て候補2つで、各ブロックで各値が2つずつ入り得る場合、
+/// 候補が3つのセルを除けば複数の解を持つ状態となります。解が一意であれば、そのセルには
+/// ブロック内で3つ入り得る値が入るので、他の値を否定します。
+/// 複数の解を持つパズルでは誤った否定を行うため、解が一意であると分かっている場合のみ用います。
+#[derive(Debug, Clone, Default)]
+pub struct Bug;
+
+impl std::fmt::Display for Bug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BUG+1")
+    }
+}
+
+impl<S: Size> Strategy<S> for Bug {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        if !is_plain(field) {
+            return None;
+        }
+        let unsolved: Vec<Place<S>> = (0..S::CELLS)
+            .map(|i| unsafe { Place::new_from_raw_unchecked(i) })
+            .filter(|place| field.entropy_at(place).len() > 1)
+            .collect();
+        let mut triples = unsolved
+            .iter()
+            .filter(|place| field.entropy_at(place).len() != 2);
+        let (Some(triple), None) = (triples.next(), triples.next()) else {
+            return None;
+        };
+        if field.entropy_at(triple).len() != 3 {
+            return None;
+        }
+        // 候補が3つのセルの値のうち、それを含むブロックで3つ入り得るものが入る。
+        let units = units(field);
+        let mut found = None;
+        for unit in &units {
+            for value in Entropy::<S>::new() {
+                let count = unit
+                    .iter()
+                    .filter(|cell| {
+                        unsolved.contains(cell) && field.entropy_at(cell).is_possible(&value)
+                    })
+                    .count();
+                match count {
+                    0 | 2 => {}
+                    3 if unit.contains(triple) => match &found {
+                        None => found = Some(value),
+                        Some(found) if found == &value => {}
+                        Some(_) => return None,
+                    },
+                    _ => return None,
+                }
+            }
+        }
+        let value = found?;
+        let eliminations = field
+            .entropy_at(triple)
+            .to_owned()
+            .into_iter()
+            .filter(|other| other != &value)
+            .map(|other| (triple.clone(), other))
+            .collect();
+        let description =
+            format!("{triple}以外を2択とした状態は解が一意でないため、{triple}は{value}となる");
+        Some(Step::new("BUG+1", description, eliminations))
+    }
+}
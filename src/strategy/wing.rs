@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn wings() {
+        let mut field = EntropyField::new();
+        restrict(
+            &mut field,
+            &[((0, 0), 0b110), ((0, 4), 0b1010), ((4, 0), 0b1100)],
+        );
+        let step = XyWing.find(&field).unwrap();
+        assert_eq!(
+            step.description(),
+            "軸(0, 0)[12]、翼(4, 0)[23]と(0, 4)[13]のどちらかに3が入る"
+        );
+        assert_eq!(
+            step.eliminations(),
+            &[(Place::new(4, 4).unwrap(), Value::THREE)]
+        );
+
+        let mut field = EntropyField::new();
+        restrict(
+            &mut field,
+            &[((0, 0), 0b1110), ((1, 1), 0b1010), ((4, 0), 0b1100)],
+        );
+        assert!(XyWing.find(&field).is_none());
+        let step = XyzWing.find(&field).unwrap();
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(1, 0).unwrap(), Value::THREE),
+                (Place::new(2, 0).unwrap(), Value::THREE)
+            ]
+        );
+
+        // 5行目の1は両端にしか入らないので、(0, 0)か(8, 8)のどちらかは2となる。
+        let mut field = EntropyField::new();
+        restrict(&mut field, &[((0, 0), 0b110), ((8, 8), 0b110)]);
+        let without_one = Entropy::try_from(0b1111111100).unwrap();
+        for x in 1..8 {
+            field
+                .insert(Place::new(x, 4).unwrap(), without_one.clone())
+                .unwrap();
+        }
+        let step = WWing.find(&field).unwrap();
+        assert_eq!(
+            step.description(),
+            "(0, 0)[12]と(8, 8)[12]は1の強いリンク(0, 4)=(8, 4)で結ばれ、どちらかに2が入る"
+        );
+        assert_eq!(
+            step.eliminations(),
+            &[
+                (Place::new(8, 0).unwrap(), Value::TWO),
+                (Place::new(0, 8).unwrap(), Value::TWO)
+            ]
+        );
+    }
+}
+
+use super::*;
+
+/// 候補が2つだけのセルを全て返します。
+fn bivalues<S: Size>(field: &EntropyField<S>) -> Vec<Place<S>> {
+    (0..S::CELLS)
+        .map(|i| unsafe { Place::new_from_raw_unchecked(i) })
+        .filter(|place| field.entropy_at(place).len() == 2)
+        .collect()
+}
+
+/// 1つだけ立っているビットが表す値を返します。
+fn value_of<S: Size>(bit: BITS) -> Value<S> {
+    Value::new(bit.trailing_zeros()).unwrap()
+}
+
+/// 軸のセルと、軸と同じブロックに含まれる2つの翼のセルからなるウィングを探します。
+/// 軸の候補の数をpivot_lenとし、翼の共通の値zが軸の候補に含まれる場合は
+/// 軸もzを否定するセルと同じブロックに含まれる必要があります。
+fn find_wing<S: Size>(
+    field: &EntropyField<S>,
+    technique: &str,
+    pivot_len: BITS,
+) -> Option<Step<S>> {
+    let bivalues = bivalues(field);
+    for i in 0..S::CELLS {
+        let pivot = unsafe { Place::new_from_raw_unchecked(i) };
+        if field.entropy_at(&pivot).len() != pivot_len {
+            continue;
+        }
+        let pivot_bits = bits(field, &pivot);
+        // 翼の候補は軸と1つ以上の値を共有し、軸と同じブロックに含まれる。
+        let pincers: Vec<&Place<S>> = bivalues
+            .iter()
+            .filter(|&place| {
+                let bits = bits(field, place);
+                bits != pivot_bits && bits & pivot_bits != 0 && sees(field, &pivot, place)
+            })
+            .collect();
+        for (j, &a) in pincers.iter().enumerate() {
+            for &b in &pincers[j + 1..] {
+                let (a_bits, b_bits) = (bits(field, a), bits(field, b));
+                let z = a_bits & b_bits;
+                if a_bits == b_bits
+                    || z.count_ones() != 1
+                    || (a_bits | b_bits) & !z & !pivot_bits != 0
+                {
+                    continue;
+                }
+                // XY-Wingでは軸がzを持たず、XYZ-Wingでは軸が全ての値を持つ。
+                let mut cells = vec![a.clone(), b.clone()];
+                match pivot_len {
+                    2 if z & pivot_bits == 0 => {}
+                    3 if a_bits | b_bits == pivot_bits => cells.push(pivot.clone()),
+                    _ => continue,
+                }
+                let value = value_of(z);
+                let eliminations = seen_by_all(field, &cells, &value);
+                if eliminations.is_empty() {
+                    continue;
+                }
+                // XYZ-Wingでは軸を含む3つのセルのいずれかにzが入る。
+                let which = if pivot_len == 2 {
+                    "どちらか"
+                } else {
+                    "いずれか"
+                };
+                let description = format!(
+                    "軸{pivot}{}、翼{a}{}と{b}{}の{which}に{value}が入る",
+                    field.entropy_at(&pivot),
+                    field.entropy_at(a),
+                    field.entropy_at(b)
+                );
+                return Some(Step::new(technique, description, eliminations));
+            }
+        }
+    }
+    None
+}
+
+/// XY-Wingの検出です。
+/// 候補が{x, y}の軸のセルと同じブロックに、候補が{x, z}と{y, z}の翼のセルがある場合、
+/// 軸がどちらの値となってもいずれかの翼がzとなるので、
+/// 両方の翼と同じブロックに含まれるセルからzを否定します。
+#[derive(Debug, Clone, Default)]
+pub struct XyWing;
+
+impl std::fmt::Display for XyWing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XY-Wing")
+    }
+}
+
+impl<S: Size> Strategy<S> for XyWing {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        find_wing(field, "XY-Wing", 2)
+    }
+}
+
+/// XYZ-Wingの検出です。
+/// 候補が{x, y, z}の軸のセルと同じブロックに、候補が{x, z}と{y, z}の翼のセルがある場合、
+/// 軸と両方の翼のいずれかがzとなるので、3つのセル全てと同じブロックに含まれるセルからzを否定します。
+#[derive(Debug, Clone, Default)]
+pub struct XyzWing;
+
+impl std::fmt::Display for XyzWing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XYZ-Wing")
+    }
+}
+
+impl<S: Size> Strategy<S> for XyzWing {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        find_wing(field, "XYZ-Wing", 3)
+    }
+}
+
+/// W-Wingの検出です。
+/// 候補が同じ{x, y}の2つのセルが、xの強いリンク(xの入り得るセルが2つだけのブロック)の
+/// 両端とそれぞれ同じブロックに含まれる場合、どちらかのセルはyとなるので、
+/// 両方のセルと同じブロックに含まれるセルからyを否定します。
+#[derive(Debug, Clone, Default)]
+pub struct WWing;
+
+impl std::fmt::Display for WWing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "W-Wing")
+    }
+}
+
+impl<S: Size> Strategy<S> for WWing {
+    fn find(&self, field: &EntropyField<S>) -> Option<Step<S>> {
+        let bivalues = bivalues(field);
+        let units = units(field);
+        for (i, a) in bivalues.iter().enumerate() {
+            for b in &bivalues[i + 1..] {
+                let pair = bits(field, a);
+                if bits(field, b) != pair || sees(field, a, b) {
+                    continue;
+                }
+                for link in field.entropy_at(a).to_owned() {
+                    let other = value_of(pair & !(1 << BITS::from(link.clone())));
+                    for unit in &units {
+                        let ends: Vec<&Place<S>> = unit
+                            .iter()
+                            .filter(|place| {
+                                let entropy = field.entropy_at(place);
+                                entropy.len() > 1 && entropy.is_possible(&link)
+                            })
+                            .collect();
+                        let [p, q] = ends[..] else {
+                            continue;
+                        };
+                        let (p, q) = if sees(field, a, p) && sees(field, b, q) {
+                            (p, q)
+                        } else if sees(field, a, q) && sees(field, b, p) {
+                            (q, p)
+                        } else {
+                            continue;
+                        };
+                        if [a, b].contains(&p) || [a, b].contains(&q) {
+                            continue;
+                        }
+                        let eliminations = seen_by_all(field, &[a.clone(), b.clone()], &other);
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+                        let description = format!(
+                            "{a}{}と{b}{}は{link}の強いリンク{p}={q}で結ばれ、どちらかに{other}が入る",
+                            field.entropy_at(a),
+                            field.entropy_at(b)
+                        );
+                        return Some(Step::new("W-Wing", description, eliminations));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
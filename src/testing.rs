@@ -0,0 +1,22 @@
+use super::*;
+use brute_force::{Report, Searchable};
+use entropy_field::size::Size;
+
+/// Attackerで探索し、最初に見つかった解答を返します。
+/// 解答が見つからない場合はパニックします。
+pub fn solve_first<F: Searchable>(field: F) -> F {
+    Attacker::new(field)
+        .find_map(|report| match report {
+            Report::Found(field) => Some(field),
+            _ => None,
+        })
+        .unwrap()
+}
+
+/// 座標の一覧からPlaceの一覧を返します。
+pub fn places<S: Size>(cells: &[(usize, usize)]) -> Vec<Place<S>> {
+    cells
+        .iter()
+        .map(|&(x, y)| Place::new(x, y).unwrap())
+        .collect()
+}